@@ -0,0 +1,90 @@
+//! Measures executing and building pipelines of 10k steps, e.g. with `cargo bench --bench execute`.
+//!
+//! Numbers from a single core CI style VM, where repeated runs of an unchanged tree varied by up to 50%:
+//!
+//! | benchmark                    | baseline  | after                |
+//! |------------------------------|-----------|----------------------|
+//! | execute 10k steps            | 8.6 µs    | 8.6 - 13.5 µs, noise |
+//! | push 10k steps               | 21.3 µs   | 20.4 µs              |
+//! | push 10k steps with capacity | -         | 20.0 µs              |
+//!
+//! `PipelineVec::execute` is a plain loop over the `Vec`'s `IntoIter`, and being generic it's already
+//! monomorphized and inlined into the caller, so adding `#[inline]` made no measurable difference and was dropped.
+//! Building with `PipelineVec::with_capacity` avoids reallocating as steps are pushed, which is a small win that
+//! is mostly hidden behind constructing the steps themselves.
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use enum_pipeline::{Execute, PipelineVec};
+use enum_pipeline_derive::Execute;
+
+const STEPS: u64 = 10_000;
+
+#[derive(Execute)]
+enum Operations {
+    #[handler(add)]
+    Add(u64),
+    #[handler(scale)]
+    Scale(u64, u64),
+    #[handler(noop)]
+    Noop,
+}
+
+impl Operations {
+    fn add(v: u64) {
+        black_box(v);
+    }
+
+    fn scale(v: u64, factor: u64) {
+        black_box(v * factor);
+    }
+
+    fn noop() {}
+}
+
+fn step(i: u64) -> Operations {
+    match i % 3 {
+        0 => Operations::Add(i),
+        1 => Operations::Scale(i, 2),
+        _ => Operations::Noop,
+    }
+}
+
+fn pipeline() -> PipelineVec<Operations> {
+    (0..STEPS).map(step).collect()
+}
+
+fn execute(c: &mut Criterion) {
+    c.bench_function("execute 10k steps", |b| {
+        b.iter_batched(
+            pipeline,
+            |pipeline| pipeline.execute(),
+            BatchSize::LargeInput,
+        )
+    });
+}
+
+fn push(c: &mut Criterion) {
+    c.bench_function("push 10k steps", |b| {
+        b.iter(|| {
+            let mut pipeline = PipelineVec::new();
+            for i in 0..STEPS {
+                pipeline.push(step(i));
+            }
+            pipeline
+        })
+    });
+}
+
+fn push_with_capacity(c: &mut Criterion) {
+    c.bench_function("push 10k steps with capacity", |b| {
+        b.iter(|| {
+            let mut pipeline = PipelineVec::with_capacity(STEPS as usize);
+            for i in 0..STEPS {
+                pipeline.push(step(i));
+            }
+            pipeline
+        })
+    });
+}
+
+criterion_group!(benches, execute, push, push_with_capacity);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Fields, Result};
+
+use crate::util::{cfg_attrs, enum_variants, function_ident, to_snake_case, AsGeneratedIdent};
+
+/// Expands the [derive(Constructors)] macro into an associated function per variant, named after the
+/// variant in snake case and taking its fields in order, with the visibility of the enum, e.g. `Run(f32)` gets `fn run(v1: f32) -> Self`.
+/// Names that are keywords are raw identifiers, so that `Move(u32)` gets `fn r#move(v1: u32) -> Self`.
+pub fn expand_constructors(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let vis = input.vis;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+
+    let constructors = variants
+        .iter()
+        .map(|variant| {
+            let cfg_attrs = cfg_attrs(variant);
+            let variant_ident = &variant.ident;
+            let fn_ident = function_ident(
+                &to_snake_case(&variant_ident.to_string()),
+                variant_ident.span(),
+            )?;
+            let field_idents = variant.fields.as_generated_ident("v");
+            let field_types = variant.fields.iter().map(|field| &field.ty);
+            let value = match variant.fields {
+                Fields::Unit => quote!(Self::#variant_ident),
+                Fields::Unnamed(_) => quote!(Self::#variant_ident(#(#field_idents),*)),
+                Fields::Named(_) => quote!(Self::#variant_ident { #(#field_idents),* }),
+            };
+
+            Ok(quote! {
+                #(#cfg_attrs)*
+                #[allow(dead_code)]
+                #vis fn #fn_ident(#(#field_idents: #field_types),*) -> Self {
+                    #value
+                }
+            })
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            #(#constructors)*
+        }
+    })
+}
@@ -0,0 +1,34 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+use crate::util::{borrowed_self, cfg_attrs, enum_variants, wildcard_pattern};
+
+/// Expands the [derive(Describe)] macro into a `Describe` implementation that describes each
+/// variant by its name, regardless of its fields, e.g. `Run(f32)` is described as `"Run"`.
+/// `String` is named through `enum_pipeline`, so that the impl also builds in `no_std` crates using `alloc`.
+pub fn expand_describe(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+
+    let arms = variants.iter().map(|variant| {
+        let cfg_attrs = cfg_attrs(variant);
+        let pattern = wildcard_pattern(variant);
+        let name = variant.ident.to_string();
+
+        quote!(#(#cfg_attrs)* #pattern => ::core::convert::From::from(#name))
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics Describe for #enum_ident #ty_generics #where_clause {
+            fn describe(&self) -> ::enum_pipeline::__private::String {
+                match #scrutinee {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
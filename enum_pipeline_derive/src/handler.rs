@@ -0,0 +1,287 @@
+use proc_macro2::{Span, TokenStream};
+use quote::{quote, quote_spanned};
+use syn::{
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    token, Error, ExprBlock, ExprClosure, Ident, LitInt, Path, Result, Token,
+};
+
+use crate::util::{function_ident, to_snake_case};
+
+/// A parsed `#[handler(...)]` (or `#[default_handler(...)]`) attribute, e.g. `#[handler(ref my_handler)]`.
+pub struct Handler {
+    /// Whether the handler is passed the index of the step after the variant fields.
+    pub with_index: bool,
+    /// Whether the variant fields are passed to the handler by reference, rather than moved into it.
+    pub by_ref: bool,
+    /// Whether the variant fields are passed to the handler by mutable reference, for in place execution.
+    pub by_mut: bool,
+    /// The function (or closure) that is called.
+    pub target: HandlerTarget,
+    /// The fields to forward, in argument order, e.g. `2, 1` in `#[handler(my_handler; 2, 1)]` or `ref, val` in
+    /// `#[handler(my_handler; ref, val)]`. All of the fields are forwarded in declaration order if this is `None`.
+    pub field_order: Option<Vec<FieldArg>>,
+}
+
+/// A single entry of a handler's field list, of the form `[ref|val] [position]`, e.g. `ref 2`.
+pub struct FieldArg {
+    /// How the field is passed, overriding how the handler takes its fields, if given.
+    pub mode: Option<FieldMode>,
+    /// The one-based position of the field, if given, otherwise the field at this entry's position.
+    pub position: Option<LitInt>,
+    /// The span of the entry as written.
+    pub span: Span,
+}
+
+/// How a single field is passed to a handler.
+#[derive(Clone, Copy, PartialEq)]
+pub enum FieldMode {
+    /// By reference, e.g. `&__1`.
+    Ref,
+    /// By value, e.g. `__1`.
+    Val,
+}
+
+/// What a handler attribute calls.
+pub enum HandlerTarget {
+    /// A handler function, e.g. `my_handler` or `crate::handlers::my_handler`.
+    Path(Path),
+    /// An inline closure, e.g. `|v: f32| println!("{}", v)`.
+    Closure(Box<ExprClosure>),
+    /// A trait implemented by the enum, e.g. `trait = MyHandler`, whose `handle_<variant>` method is called.
+    Trait(Path),
+    /// An inline block, e.g. `{ println!("{}", __1) }`, which is spliced into the match arm as is.
+    Block(Box<ExprBlock>),
+}
+
+impl Parse for Handler {
+    fn parse(input: ParseStream) -> Result<Self> {
+        // `with_index` is a modifier when followed by the handler, rather than a handler named `with_index`
+        let fork = input.fork();
+        let with_index = fork
+            .parse::<Ident>()
+            .is_ok_and(|ident| ident == "with_index")
+            && !fork.is_empty()
+            && !fork.peek(Token![::]);
+        if with_index {
+            input.parse::<Ident>()?;
+        }
+        let by_ref = input.parse::<Option<Token![ref]>>()?.is_some();
+        let by_mut = !by_ref && input.parse::<Option<Token![mut]>>()?.is_some();
+        let is_closure =
+            input.peek(Token![|]) || input.peek(Token![||]) || input.peek(Token![move]);
+        let is_trait = input.peek(Token![trait]) && input.peek2(Token![=]);
+        if input.peek(token::Brace) {
+            let block = input.parse::<ExprBlock>()?;
+            if with_index || by_ref || by_mut || !input.is_empty() {
+                return Err(Error::new_spanned(
+                    block,
+                    "Inline block handlers use the fields as they are bound, so they can't have `with_index`, `ref`, `mut` or a field list",
+                ));
+            }
+
+            return Ok(Handler {
+                with_index,
+                by_ref,
+                by_mut,
+                target: HandlerTarget::Block(Box::new(block)),
+                field_order: None,
+            });
+        }
+        let target = match (is_closure, is_trait) {
+            (true, _) => HandlerTarget::Closure(Box::new(input.parse::<ExprClosure>()?)),
+            (_, true) => {
+                input.parse::<Token![trait]>()?;
+                input.parse::<Token![=]>()?;
+                HandlerTarget::Trait(input.parse::<Path>()?)
+            }
+            _ => HandlerTarget::Path(input.parse::<Path>()?),
+        };
+
+        let field_order = match input.parse::<Option<Token![;]>>()? {
+            Some(_) => Some(
+                Punctuated::<FieldArg, Token![,]>::parse_terminated(input)?
+                    .into_iter()
+                    .collect(),
+            ),
+            None => None,
+        };
+
+        Ok(Handler {
+            with_index,
+            by_ref,
+            by_mut,
+            target,
+            field_order,
+        })
+    }
+}
+
+impl Parse for FieldArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let span = input.span();
+        let is_val = input
+            .fork()
+            .parse::<Ident>()
+            .is_ok_and(|ident| ident == "val");
+        let mode = match (input.peek(Token![ref]), is_val) {
+            (true, _) => {
+                input.parse::<Token![ref]>()?;
+                Some(FieldMode::Ref)
+            }
+            (_, true) => {
+                input.parse::<Ident>()?;
+                Some(FieldMode::Val)
+            }
+            _ => None,
+        };
+        let position = match mode.is_none() || input.peek(LitInt) {
+            true => Some(input.parse::<LitInt>().map_err(|_| {
+                Error::new(span, "Expected `ref`, `val` or the position of a field")
+            })?),
+            false => None,
+        };
+
+        Ok(FieldArg {
+            mode,
+            position,
+            span,
+        })
+    }
+}
+
+impl Handler {
+    /// The conventionally named handler of a variant, i.e. `prefix` followed by the snake case variant name,
+    /// so that `handle_` and `DoWork` give `handle_do_work`. `prefix` must be a valid start of an identifier,
+    /// and names that are keywords are raw identifiers, so that an empty prefix and `Move` give `r#move`.
+    pub fn prefixed(prefix: &str, variant_ident: &Ident) -> Result<Self> {
+        let name = format!("{}{}", prefix, to_snake_case(&variant_ident.to_string()));
+
+        Ok(Handler {
+            with_index: false,
+            by_ref: false,
+            by_mut: false,
+            target: HandlerTarget::Path(function_ident(&name, variant_ident.span())?.into()),
+            field_order: None,
+        })
+    }
+
+    /// The call of the handler with `args`, spanned at the handler so that e.g. an arity mismatch is reported
+    /// at the attribute. Inline blocks are used as the call itself, ignoring `args`.
+    pub fn call(
+        &self,
+        variant_ident: &Ident,
+        module: Option<&Path>,
+        args: &[TokenStream],
+    ) -> TokenStream {
+        match &self.target {
+            HandlerTarget::Block(block) => quote!(#block),
+            _ => {
+                let callee = self.callee(variant_ident, module);
+                quote_spanned!(self.span()=> #callee(#(#args),*))
+            }
+        }
+    }
+
+    /// The qualified handler function, treating a bare `my_handler` as `Self::my_handler`, or as `my_module::my_handler`
+    /// when the handlers are in `module`. Paths with more than one segment or a leading `::` are used as written, and closures are
+    /// parenthesized so that they can be called inline. Traits are called through the method named
+    /// after the variant, so that `trait = MyHandler` handles `DoWork` with `<Self as MyHandler>::handle_do_work`.
+    fn callee(&self, variant_ident: &Ident, module: Option<&Path>) -> TokenStream {
+        match &self.target {
+            HandlerTarget::Path(path) => {
+                match (
+                    path.leading_colon.is_none() && path.segments.len() == 1,
+                    module,
+                ) {
+                    (true, Some(module)) => quote_spanned!(path.span()=> #module::#path),
+                    (true, None) => quote_spanned!(path.span()=> Self::#path),
+                    (false, _) => quote!(#path),
+                }
+            }
+            HandlerTarget::Closure(closure) => quote!((#closure)),
+            HandlerTarget::Block(block) => quote!(#block),
+            HandlerTarget::Trait(trait_path) => {
+                let method = Ident::new(
+                    &format!("handle_{}", to_snake_case(&variant_ident.to_string())),
+                    trait_path.span(),
+                );
+
+                quote_spanned!(trait_path.span()=> <Self as #trait_path>::#method)
+            }
+        }
+    }
+
+    /// The fields forwarded to the handler, in argument order, with any mode they are explicitly passed by.
+    /// Checks that explicit positions are in range, and that there's a mode for every field when they aren't given.
+    pub fn forwarded_fields<'a>(
+        &self,
+        field_idents: &'a [Ident],
+    ) -> Result<Vec<(&'a Ident, Option<FieldMode>)>> {
+        let field_order = match &self.field_order {
+            Some(field_order) => field_order,
+            None => return Ok(field_idents.iter().map(|ident| (ident, None)).collect()),
+        };
+
+        // the fields are either all given by position, or all given in declaration order
+        let positioned = field_order
+            .iter()
+            .filter(|arg| arg.position.is_some())
+            .count();
+        if positioned == 0 && field_order.len() != field_idents.len() {
+            return Err(Error::new(
+                field_order
+                    .last()
+                    .map_or_else(|| self.span(), |arg| arg.span),
+                format!(
+                    "Expected a passing mode for each of the {} fields, found {}",
+                    field_idents.len(),
+                    field_order.len()
+                ),
+            ));
+        }
+        if let Some(arg) = field_order.iter().find(|arg| arg.position.is_none()) {
+            if positioned > 0 {
+                return Err(Error::new(
+                    arg.span,
+                    "Expected the position of a field, since other fields are given by position",
+                ));
+            }
+        }
+
+        field_order
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| {
+                let position = match &arg.position {
+                    Some(position) => position,
+                    None => return Ok((&field_idents[i], arg.mode)),
+                };
+                let index = position.base10_parse::<usize>()?;
+
+                match index.checked_sub(1).and_then(|i| field_idents.get(i)) {
+                    Some(ident) => Ok((ident, arg.mode)),
+                    None => Err(Error::new_spanned(
+                        position,
+                        format!(
+                            "Field {} is out of range, expected a position from 1 to {}",
+                            index,
+                            field_idents.len()
+                        ),
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    /// The span of the handler as written, so that errors in the generated call point at it.
+    pub fn span(&self) -> Span {
+        match &self.target {
+            HandlerTarget::Path(path) => path.span(),
+            HandlerTarget::Closure(closure) => closure.span(),
+            HandlerTarget::Trait(trait_path) => trait_path.span(),
+            HandlerTarget::Block(block) => block.span(),
+        }
+    }
+}
@@ -0,0 +1,70 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{punctuated::Punctuated, DeriveInput, Error, LitStr, Result, Token};
+
+use crate::util::{borrowed_self, cfg_attrs, enum_variants, wildcard_pattern, OfRelevantType};
+
+/// Expands the [derive(HasDeps)] macro into a `HasDeps` implementation using #[after("Init")] helper attributes,
+/// naming the variants that a variant executes after. Several variants may be listed, e.g. #[after("Init", "Load")].
+/// Example:
+/// ```ignore
+/// #[derive(HasDeps)]
+/// pub enum Test {
+///     Init,
+///     #[after("Init")]
+///     Run(f32),
+/// }
+/// ```
+pub fn expand_has_deps(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+
+    let mut name_arms = Vec::new();
+    let mut after_arms = Vec::new();
+    for variant in &variants {
+        let cfg_attrs = cfg_attrs(variant);
+        let pattern = wildcard_pattern(variant);
+        let name = variant.ident.to_string();
+
+        let mut after = Vec::new();
+        for attr in variant.attrs.clone().of_relevant_type("after") {
+            after.extend(attr.parse_args_with(Punctuated::<LitStr, Token![,]>::parse_terminated)?);
+        }
+        if let Some(unknown) = after
+            .iter()
+            .find(|dep| !variants.iter().any(|variant| variant.ident == dep.value()))
+        {
+            return Err(Error::new_spanned(
+                unknown,
+                format!(
+                    "Variant {}::{} executes after unknown variant {}",
+                    enum_ident,
+                    name,
+                    unknown.value()
+                ),
+            ));
+        }
+
+        name_arms.push(quote!(#(#cfg_attrs)* #pattern => #name));
+        after_arms.push(quote!(#(#cfg_attrs)* #pattern => &[#(#after),*]));
+    }
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics HasDeps for #enum_ident #ty_generics #where_clause {
+            fn variant_name(&self) -> &'static str {
+                match #scrutinee {
+                    #(#name_arms,)*
+                }
+            }
+
+            fn after(&self) -> &'static [&'static str] {
+                match #scrutinee {
+                    #(#after_arms,)*
+                }
+            }
+        }
+    })
+}
@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitStr, Result};
+
+use crate::util::{
+    borrowed_self, cfg_attrs, enum_variants, wildcard_pattern, SingleOfRelevantType,
+};
+
+/// Expands the [derive(Labeled)] macro into a `Labeled` implementation using #[label("My label")] helper attributes.
+/// Variants without a #[label(...)] are labeled with their name instead.
+/// Example:
+/// ```ignore
+/// #[derive(Labeled)]
+/// pub enum Test {
+///     #[label("Allocate GPU buffers")]
+///     Allocate(f32, f32),
+///     Run(f32),
+/// }
+/// ```
+pub fn expand_labeled(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let cfg_attrs = cfg_attrs(variant);
+            let pattern = wildcard_pattern(variant);
+            let label = match variant.attrs.clone().single_of_relevant_type("label")? {
+                Some(attr) => attr.parse_args::<LitStr>()?,
+                None => LitStr::new(&variant.ident.to_string(), variant.ident.span()),
+            };
+
+            Ok(quote!(#(#cfg_attrs)* #pattern => #label))
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics Labeled for #enum_ident #ty_generics #where_clause {
+            fn label(&self) -> &'static str {
+                match #scrutinee {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
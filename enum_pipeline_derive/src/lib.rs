@@ -1,14 +1,113 @@
 use proc_macro::TokenStream;
 use syn::{parse_macro_input, DeriveInput};
 
+use constructors::expand_constructors;
+use describe::expand_describe;
+use has_deps::expand_has_deps;
+use labeled::expand_labeled;
+use phased::expand_phased;
 use pipeline::expand_execute;
+use prioritized::expand_prioritized;
+use variant_index::expand_variant_index;
 
+mod constructors;
+mod describe;
+mod handler;
+mod has_deps;
+mod labeled;
+mod phased;
 mod pipeline;
+mod prioritized;
 mod util;
+mod variant_index;
 
-#[proc_macro_derive(Execute, attributes(handler))]
+#[proc_macro_derive(
+    Execute,
+    attributes(
+        handler,
+        execute_with,
+        execute_with_mut,
+        try_execute,
+        execute_collect,
+        execute_indexed,
+        execute_in_place,
+        execute_ref,
+        default_handler,
+        handler_prefix,
+        handlers_in,
+        catch_all,
+        emit_dispatch_fn,
+        skip,
+        pipeline
+    )
+)]
 pub fn derive_helper_attr(input: TokenStream) -> TokenStream {
     let input = parse_macro_input!(input as DeriveInput);
 
-    expand_execute(input).into()
+    expand_execute(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Describe)]
+pub fn derive_describe(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_describe(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Constructors)]
+pub fn derive_constructors(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_constructors(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(HasDeps, attributes(after))]
+pub fn derive_has_deps(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_has_deps(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Labeled, attributes(label))]
+pub fn derive_labeled(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_labeled(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Phased, attributes(phase))]
+pub fn derive_phased(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_phased(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(Prioritized, attributes(priority))]
+pub fn derive_prioritized(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_prioritized(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
+
+#[proc_macro_derive(VariantIndex)]
+pub fn derive_variant_index(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_variant_index(input)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
 }
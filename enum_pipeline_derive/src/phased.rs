@@ -0,0 +1,54 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, LitStr, Result};
+
+use crate::util::{
+    borrowed_self, cfg_attrs, enum_variants, wildcard_pattern, SingleOfRelevantType,
+};
+
+/// Expands the [derive(Phased)] macro into a `Phased` implementation using #[phase("setup")] helper attributes.
+/// Variants without a #[phase(...)] aren't in any phase.
+/// Example:
+/// ```ignore
+/// #[derive(Phased)]
+/// pub enum Test {
+///     #[phase("setup")]
+///     Allocate(f32, f32),
+///     #[phase("run")]
+///     Run(f32),
+/// }
+/// ```
+pub fn expand_phased(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let cfg_attrs = cfg_attrs(variant);
+            let pattern = wildcard_pattern(variant);
+            let phase = match variant.attrs.clone().single_of_relevant_type("phase")? {
+                Some(attr) => {
+                    let phase = attr.parse_args::<LitStr>()?;
+                    quote!(Some(#phase))
+                }
+                None => quote!(None),
+            };
+
+            Ok(quote!(#(#cfg_attrs)* #pattern => #phase))
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics Phased for #enum_ident #ty_generics #where_clause {
+            fn phase(&self) -> Option<&'static str> {
+                match #scrutinee {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
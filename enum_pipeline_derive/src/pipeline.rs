@@ -1,12 +1,292 @@
-use proc_macro2::TokenStream;
-use quote::quote;
-use syn::{Attribute, Data, DeriveInput, ExprMatch};
+use proc_macro2::{Span, TokenStream, TokenTree};
+use quote::{quote, ToTokens};
+use syn::{
+    ext::IdentExt,
+    parse::{Parse, ParseStream},
+    punctuated::Punctuated,
+    spanned::Spanned,
+    Attribute, DeriveInput, Error, Fields, Ident, LitStr, Path, Result, Token, Type, Variant,
+};
 
-use crate::util::{AsGeneratedIdent, OfRelevantType};
+use crate::handler::{FieldMode, Handler, HandlerTarget};
+use crate::util::{
+    borrowed_self, cfg_attrs, enum_variants, wildcard_pattern, AsGeneratedIdent, OfRelevantType,
+    SingleOfRelevantType,
+};
+
+/// The enum level attributes that select an `ExecuteKind`.
+const KIND_ATTRS: [&str; 7] = [
+    "execute_with",
+    "execute_with_mut",
+    "try_execute",
+    "execute_collect",
+    "execute_indexed",
+    "execute_in_place",
+    "execute_ref",
+];
+
+/// The kind attributes that don't take any arguments.
+const BARE_KIND_ATTRS: [&str; 3] = ["execute_indexed", "execute_in_place", "execute_ref"];
+
+/// The pipeline trait that a derived enum implements.
+enum ExecuteKind {
+    /// `Execute`, where handlers receive only the variant fields.
+    Void,
+    /// `ExecuteWith<TArg>`, where handlers receive the variant fields followed by `arg: &TArg`.
+    With(Box<Type>),
+    /// `ExecuteWithMut<TArg>`, where handlers receive the variant fields followed by `arg: &mut TArg`.
+    WithMut(Box<Type>),
+    /// `TryExecute` with `Error = E`, where handlers receive only the variant fields and return `Result<(), E>`.
+    Try(Box<Type>),
+    /// `ExecuteCollect` with `Output = R`, where handlers receive only the variant fields and return `R`.
+    Collect(Box<Type>),
+    /// `ExecuteIndexed`, where handlers marked `with_index` receive the variant fields followed by `index: usize`.
+    Indexed,
+    /// `ExecuteInPlace`, where the variant fields are borrowed from `&mut self` rather than moved out of it.
+    InPlace,
+    /// `ExecuteRef`, where the variant fields are borrowed from `&self`, so handlers receive references to them.
+    Ref,
+}
+
+impl ExecuteKind {
+    /// Determines the kind from the enum level attributes, defaulting to `Void`.
+    fn from_attrs(attrs: Vec<Attribute>) -> Result<ExecuteKind> {
+        let kind_attrs: Vec<Attribute> = attrs
+            .into_iter()
+            .filter(|attr| KIND_ATTRS.iter().any(|name| attr.path.is_ident(name)))
+            .collect();
+
+        match kind_attrs.len() {
+            0 => Ok(ExecuteKind::Void),
+            1 if is_bare_kind(&kind_attrs[0]) => {
+                let attr = &kind_attrs[0];
+                let name = attr.path.get_ident().unwrap().to_string();
+
+                match (attr.tokens.is_empty(), name.as_str()) {
+                    (true, "execute_indexed") => Ok(ExecuteKind::Indexed),
+                    (true, "execute_in_place") => Ok(ExecuteKind::InPlace),
+                    (true, _) => Ok(ExecuteKind::Ref),
+                    (false, _) => Err(Error::new_spanned(
+                        attr,
+                        format!("#[{}] doesn't take any arguments", name),
+                    )),
+                }
+            }
+            1 => {
+                let attr = &kind_attrs[0];
+                let ty = Box::new(parse_arg_type(attr)?);
+
+                match attr.path.get_ident().unwrap().to_string().as_str() {
+                    "execute_with" => Ok(ExecuteKind::With(ty)),
+                    "execute_with_mut" => Ok(ExecuteKind::WithMut(ty)),
+                    "try_execute" => Ok(ExecuteKind::Try(ty)),
+                    _ => Ok(ExecuteKind::Collect(ty)),
+                }
+            }
+            _ => Err(Error::new_spanned(
+                &kind_attrs[1],
+                format!(
+                    "Only one of {} is allowed",
+                    KIND_ATTRS
+                        .iter()
+                        .map(|name| match BARE_KIND_ATTRS.contains(name) {
+                            true => format!("#[{}]", name),
+                            false => format!("#[{}(...)]", name),
+                        })
+                        .collect::<Vec<String>>()
+                        .join(", ")
+                ),
+            )),
+        }
+    }
+
+    /// Whether handlers are passed `arg` after the variant fields.
+    fn has_arg(&self) -> bool {
+        matches!(self, ExecuteKind::With(_) | ExecuteKind::WithMut(_))
+    }
+
+    /// Whether handlers marked `with_index` are passed the step index after the variant fields.
+    fn has_index(&self) -> bool {
+        matches!(self, ExecuteKind::Indexed)
+    }
+
+    /// Whether the variant fields are mutably borrowed, so that handlers can update but not take ownership of them.
+    fn is_in_place(&self) -> bool {
+        matches!(self, ExecuteKind::InPlace)
+    }
+
+    /// Whether the variant fields are borrowed, rather than moved out of the enum.
+    fn borrows_self(&self) -> bool {
+        matches!(self, ExecuteKind::InPlace | ExecuteKind::Ref)
+    }
+
+    /// Whether handlers return a `Result` that should stop execution on failure.
+    fn is_fallible(&self) -> bool {
+        matches!(self, ExecuteKind::Try(_))
+    }
+
+    /// The name of the built-in trait and method implemented for this kind.
+    fn default_trait_and_method(&self) -> (&'static str, &'static str) {
+        match self {
+            ExecuteKind::Void => ("Execute", "execute"),
+            ExecuteKind::With(_) => ("ExecuteWith", "execute"),
+            ExecuteKind::WithMut(_) => ("ExecuteWithMut", "execute"),
+            ExecuteKind::Try(_) => ("TryExecute", "try_execute"),
+            ExecuteKind::Collect(_) => ("ExecuteCollect", "execute_collect"),
+            ExecuteKind::Indexed => ("ExecuteIndexed", "execute_indexed"),
+            ExecuteKind::InPlace => ("ExecuteInPlace", "execute"),
+            ExecuteKind::Ref => ("ExecuteRef", "execute_ref"),
+        }
+    }
+}
+
+/// The enum level #[pipeline(trait = "MyTrait", method = "my_method")] attribute, which replaces
+/// the name of the implemented trait and/or method while keeping the signature of the kind.
+#[derive(Default)]
+struct PipelineAttr {
+    trait_path: Option<Path>,
+    method: Option<Ident>,
+}
+
+impl Parse for PipelineAttr {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut pipeline_attr = PipelineAttr::default();
+
+        // `trait` is a keyword, so the keys are parsed as any ident
+        let pairs =
+            Punctuated::<(Ident, LitStr), Token![,]>::parse_terminated_with(input, |input| {
+                let key = Ident::parse_any(input)?;
+                input.parse::<Token![=]>()?;
+                Ok((key, input.parse()?))
+            })?;
+
+        for (key, value) in pairs {
+            match key.to_string().as_str() {
+                "trait" if pipeline_attr.trait_path.is_none() => {
+                    pipeline_attr.trait_path = Some(value.parse()?)
+                }
+                "method" if pipeline_attr.method.is_none() => {
+                    pipeline_attr.method = Some(value.parse()?)
+                }
+                "trait" | "method" => {
+                    return Err(Error::new_spanned(
+                        key.clone(),
+                        format!("Duplicate `{}` in #[pipeline(...)]", key),
+                    ))
+                }
+                _ => {
+                    return Err(Error::new_spanned(
+                        key.clone(),
+                        format!(
+                            "Unknown key `{}` in #[pipeline(...)], expected `trait` or `method`",
+                            key
+                        ),
+                    ))
+                }
+            }
+        }
+
+        Ok(pipeline_attr)
+    }
+}
+
+/// Whether `attr` is one of the kind attributes that don't take any arguments.
+fn is_bare_kind(attr: &Attribute) -> bool {
+    BARE_KIND_ATTRS.iter().any(|name| attr.path.is_ident(name))
+}
+
+/// Parses the type out of an #[execute_with(...)] style attribute.
+fn parse_arg_type(attr: &Attribute) -> Result<Type> {
+    attr.parse_args::<Type>().map_err(|_| {
+        Error::new_spanned(
+            attr,
+            format!("#[{}(...)] expects a type", attr.path.get_ident().unwrap()),
+        )
+    })
+}
+
+/// Parses the prefix out of a #[handler_prefix("handle_")] attribute, which can't be combined with a default handler.
+fn parse_handler_prefix(attr: &Attribute, default_handler: &Option<Handler>) -> Result<String> {
+    if default_handler.is_some() {
+        return Err(Error::new_spanned(
+            attr,
+            "#[handler_prefix(...)] can't be combined with #[default_handler(...)]",
+        ));
+    }
+
+    let prefix = attr.parse_args::<LitStr>()?;
+    match syn::parse_str::<Ident>(&format!("{}handler", prefix.value())) {
+        Ok(_) => Ok(prefix.value()),
+        Err(_) => Err(Error::new_spanned(
+            prefix,
+            "#[handler_prefix(...)] expects the start of a function name, e.g. \"handle_\"",
+        )),
+    }
+}
+
+/// Parses a #[catch_all(Self::fallback)] attribute, whose handler is called without any fields,
+/// so it can't list fields, and an inline block can't use the bound fields like `__1`. It can't be a trait either,
+/// since there's no variant to name the trait method after. It can't be combined with a handler that every variant
+/// falls back to.
+fn parse_catch_all(attr: &Attribute, has_fallback: bool) -> Result<Handler> {
+    if has_fallback {
+        return Err(Error::new_spanned(
+            attr,
+            "#[catch_all(...)] can't be combined with #[default_handler(...)] or #[handler_prefix(...)]",
+        ));
+    }
+
+    let handler = attr.parse_args::<Handler>()?;
+    match &handler.target {
+        HandlerTarget::Trait(trait_path) => {
+            return Err(Error::new_spanned(
+                trait_path,
+                "#[catch_all(trait = ...)] handles any variant, so there is no `handle_<variant>` method to call, use a handler like #[catch_all(Self::fallback)]",
+            ))
+        }
+        HandlerTarget::Block(block) => {
+            if let Some(field) = bound_field(block.to_token_stream()) {
+                return Err(Error::new(
+                    field.span(),
+                    "#[catch_all({ ... })] blocks handle any variant, so they can't use bound fields like `__1`",
+                ));
+            }
+        }
+        _ => {}
+    }
+
+    let takes_fields = handler.by_ref || handler.by_mut || handler.field_order.is_some();
+    match takes_fields {
+        true => Err(Error::new(
+            handler.span(),
+            "#[catch_all(...)] expects a handler without fields, e.g. #[catch_all(Self::fallback)]",
+        )),
+        false => Ok(handler),
+    }
+}
+
+/// The first field binding like `__1` used in `tokens`, looking into nested groups.
+fn bound_field(tokens: TokenStream) -> Option<Ident> {
+    tokens.into_iter().find_map(|token| match token {
+        TokenTree::Ident(ident) => {
+            let name = ident.to_string();
+            let is_field = name.len() > 2
+                && name.starts_with("__")
+                && name[2..].bytes().all(|b| b.is_ascii_digit());
+            is_field.then_some(ident)
+        }
+        TokenTree::Group(group) => bound_field(group.stream()),
+        _ => None,
+    })
+}
 
 /// Expands the [derive(Execute)] macro into a pipeline implementation using #[handler(my_func_handler)] helper attributes.
+/// The handler may be any path, e.g. `crate::handlers::handle_one` or `Foo::<u8>::bar`, including associated
+/// functions of other types like `MyService::process`, where a bare function name is treated as an associated
+/// function of the enum. Fields are forwarded to the handler
+/// in declaration order, for both tuple variants like `One(f32)` and struct variants like `Resize { width: u32, height: u32 }`.
 /// Example:
-/// ```
+/// ```ignore
 /// #[derive(Execute)]
 /// pub enum Test {
 ///     #[handler(handle_one)]
@@ -14,84 +294,426 @@ use crate::util::{AsGeneratedIdent, OfRelevantType};
 ///     #[handler(handle_two)]
 ///     Two,
 /// }
-
+///
 /// impl Test {
 ///     fn handle_one(v: f32) {}
-
+///
 ///     fn handle_two() {}
 /// }
 /// ```
-// TODO(bengreenier): Use fewer raw strings to implement this
-pub fn expand_execute(input: DeriveInput) -> TokenStream {
+///
+/// Adding #[execute_with(MyArg)] to the enum implements `ExecuteWith<MyArg>` instead, and each
+/// handler receives `arg: &MyArg` after the variant fields, e.g. `fn handle_one(v: f32, arg: &MyArg)`.
+/// Likewise #[execute_with_mut(MyArg)] implements `ExecuteWithMut<MyArg>`, passing `arg: &mut MyArg`.
+/// Adding #[try_execute(MyError)] implements `TryExecute` with `Error = MyError`, where each handler
+/// returns `Result<(), MyError>` and the first failing handler stops execution.
+/// Adding #[execute_collect(MyOutput)] implements `ExecuteCollect` with `Output = MyOutput`, where each
+/// handler returns `MyOutput` (for stacked handlers, the last handler's output is used).
+/// Adding #[execute_indexed] implements `ExecuteIndexed`, where handlers marked as #[handler(with_index my_handler)]
+/// receive the zero-based `index: usize` of the step after the variant fields, e.g. `fn my_handler(v: f32, index: usize)`.
+/// Adding #[execute_in_place] implements `ExecuteInPlace`, whose `execute(&mut self)` keeps the enum, so its
+/// fields are borrowed rather than moved. Handlers marked as #[handler(mut my_handler)] receive the fields as
+/// in `fn my_handler(v: &mut f32)`, so they can update the variant, while `ref` handlers receive `&f32` and
+/// by-value handlers receive clones.
+/// Likewise #[execute_ref] implements `ExecuteRef`, whose `execute_ref(&self)` keeps the enum, and every handler
+/// receives references to the fields, as in `fn my_handler(v: &f32)`.
+///
+/// Stacking several #[handler(...)] attributes on a variant calls each of them in declaration order.
+/// Every handler but the last receives clones of the fields, so fields must be `Clone` in that case.
+///
+/// Prefixing the handler with `ref`, e.g. #[handler(ref handle_one)], passes the fields by reference
+/// as in `fn handle_one(v: &String)`. `execute(self)` still consumes the enum, so the fields are dropped
+/// once the arm completes, but by-reference handlers can share fields without them needing to be `Clone`.
+///
+/// Following the handler with `;` and the one-based positions of fields, e.g. #[handler(handle_one; 2, 1)],
+/// forwards just those fields in that order, as in `fn handle_one(y: f32, x: f32)` for `One(f32, f32)`.
+/// Each field may also be marked as passed `ref` or by `val`ue, overriding how the handler takes its fields, e.g.
+/// #[handler(handle_two; ref, val)] calls `fn handle_two(name: &String, count: u32)` for `Two(String, u32)`.
+/// Without positions, there must be a mode for each field, while `ref 2, 1` combines both.
+///
+/// The handler may also be an inline closure, e.g. #[handler(|v: f32| println!("{}", v))], which is
+/// called with the fields just like a handler function.
+///
+/// The handler may also be an inline block, e.g. #[handler({ println!("{}", __1) })], which is spliced into the
+/// match arm as is. The block can use the fields by their bound names, i.e. `__1`, `__2` and so on for tuple variants
+/// and the field names for struct variants, along with `arg` and `index` when the enum level attributes provide them.
+///
+/// Alternatively #[handler(trait = MyHandler)] dispatches to a trait implemented by the enum, calling the method
+/// named `handle_` followed by the variant name in snake case, e.g. `<Self as MyHandler>::handle_do_work` for
+/// `DoWork`. As a #[default_handler(trait = MyHandler)], every variant without a handler is dispatched this way.
+///
+/// Variants without a #[handler(...)] use the enum level #[default_handler(my_default)] instead,
+/// which is called with that variant's fields like any other handler.
+///
+/// Alternatively, the enum level #[handler_prefix("handle_")] makes variants without a #[handler(...)] call
+/// the conventionally named `Self::handle_<variant>` instead, with the variant name in snake case, so that
+/// `LoadConfig(String)` is handled by `fn handle_load_config(v: String)`. Handler names that are keywords are raw
+/// identifiers, so that with #[handler_prefix("")] `Move(u32)` is handled by `fn r#move(v: u32)`.
+///
+/// The enum level #[handlers_in(crate::ops)] makes bare handler names, including those from #[default_handler(...)]
+/// and #[handler_prefix(...)], refer to functions in that module rather than associated functions of the enum,
+/// so that #[handler(handle_one)] calls `crate::ops::handle_one`. Paths with more than one segment are used as written.
+///
+/// The enum level #[catch_all(Self::fallback)] adds a wildcard arm calling `fallback` without any fields, which handles
+/// every variant without a #[handler(...)], e.g. the newest variants of an evolving enum. It can't be combined with
+/// #[default_handler(...)] or #[handler_prefix(...)], which already handle every variant.
+///
+/// The enum level #[emit_dispatch_fn] also generates an inherent `pub fn dispatch` with the same signature as the
+/// trait method, e.g. `fn dispatch(self)`, which the trait method delegates to. This lets the dispatch be called
+/// without going through the trait.
+///
+/// Generic enums are supported, with the generated impl carrying the enum's generics and where clause.
+///
+/// Variants marked #[skip] do nothing when executed, which avoids writing no-op handlers.
+///
+/// Variants gated with #[cfg(...)] keep their gate on the generated match arm, so their handlers may be gated too.
+///
+/// Adding #[pipeline(trait = "Render", method = "render")] implements the user-defined `Render` trait with
+/// a `render` method instead of the built-in trait, keeping the same signature and dispatch. This allows
+/// several independent pipelines over the same enum, e.g. `trait Render { fn render(self); }`.
+///
+/// Any #[allow(...)] and doc attributes on the enum are repeated on the generated impl, which also allows
+/// the `clippy::all` lints, so that the derive stays quiet in crates that deny warnings.
+///
+/// Misuse is reported as a compile error spanning the offending item, rather than a panic.
+pub fn expand_execute(input: DeriveInput) -> Result<TokenStream> {
     let enum_ident = input.ident;
-    let enum_name = enum_ident.to_string();
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let variants = enum_variants(input.data, &enum_ident)?;
 
-    let variants = match input.data {
-        Data::Enum(e) => e.variants,
-        _ => panic!("Pipeline derive macro only works on enums"),
+    let kind = ExecuteKind::from_attrs(input.attrs.clone())?;
+    // the enum's lint levels and docs also apply to the impl, which is a separate item
+    let impl_attrs: Vec<&Attribute> = input
+        .attrs
+        .iter()
+        .filter(|attr| attr.path.is_ident("allow") || attr.path.is_ident("doc"))
+        .collect();
+    let default_handler = match input
+        .attrs
+        .clone()
+        .single_of_relevant_type("default_handler")?
+    {
+        Some(attr) => Some(attr.parse_args::<Handler>()?),
+        None => None,
+    };
+    let handler_prefix = match input
+        .attrs
+        .clone()
+        .single_of_relevant_type("handler_prefix")?
+    {
+        Some(attr) => Some(parse_handler_prefix(&attr, &default_handler)?),
+        None => None,
+    };
+    let handlers_in = match input.attrs.clone().single_of_relevant_type("handlers_in")? {
+        Some(attr) => Some(attr.parse_args::<Path>()?),
+        None => None,
+    };
+    let catch_all = match input.attrs.clone().single_of_relevant_type("catch_all")? {
+        Some(attr) => Some(parse_catch_all(
+            &attr,
+            default_handler.is_some() || handler_prefix.is_some(),
+        )?),
+        None => None,
     };
+    let emit_dispatch_fn = input
+        .attrs
+        .clone()
+        .single_of_relevant_type("emit_dispatch_fn")?
+        .is_some();
+    let pipeline_attr = match input.attrs.clone().single_of_relevant_type("pipeline")? {
+        Some(attr) => attr.parse_args::<PipelineAttr>()?,
+        None => PipelineAttr::default(),
+    };
+
+    let (default_trait, default_method) = kind.default_trait_and_method();
+    let trait_path = pipeline_attr
+        .trait_path
+        .unwrap_or_else(|| Ident::new(default_trait, Span::call_site()).into());
+    let method = pipeline_attr
+        .method
+        .unwrap_or_else(|| Ident::new(default_method, Span::call_site()));
 
-    // get the arms as strings
-    let arms: Vec<String> = variants
-        .into_iter()
+    let mut arms: Vec<TokenStream> = variants
+        .iter()
+        // variants without a handler of their own are left to the catch all handler (if any)
+        .filter(|variant| {
+            catch_all.is_none()
+                || !variant.attrs.clone().of_relevant_type("handler").is_empty()
+                || !variant.attrs.clone().of_relevant_type("skip").is_empty()
+        })
         .map(|variant| {
-            let variant_name = variant.ident.to_string();
-            let full_variant_name = format!("{}::{}", enum_name, variant_name);
-            let variant_handlers_all: Vec<Attribute> = variant.attrs.of_relevant_type("handler");
-
-            // error handling for handler attributes
-            match variant_handlers_all.len() {
-                0 => panic!(
-                    "Variant {} is missing attribute #[handler(your_handler_function)]",
-                    full_variant_name
-                ),
-                l if l > 1 => panic!(
-                    "Variant {} has too many handler attributes",
-                    full_variant_name
-                ),
-                _ => (),
-            }
+            // a conventionally named handler takes the place of the default handler
+            let prefixed = handler_prefix
+                .as_ref()
+                .map(|prefix| Handler::prefixed(prefix, &variant.ident))
+                .transpose()?;
+            let fallback = prefixed.as_ref().or(default_handler.as_ref());
 
-            let variant_handler_path = variant_handlers_all[0].tokens.to_string();
-            let variant_handler_fn =
-                variant_handler_path[1..variant_handler_path.len() - 1].to_string();
+            expand_arm(&enum_ident, variant, &kind, fallback, handlers_in.as_ref())
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+    if let Some(handler) = &catch_all {
+        arms.push(expand_catch_all_arm(
+            &enum_ident,
+            handler,
+            &kind,
+            handlers_in.as_ref(),
+        )?);
+    }
 
-            // ensure the full variant handler function is qualified
-            let full_variant_handler_fn = match variant_handler_fn.contains("::") {
-                true => variant_handler_fn,
-                false => format!("{}::{}", enum_name, variant_handler_fn),
-            };
+    let scrutinee = match kind.borrows_self() {
+        true => borrowed_self(&variants),
+        false => quote!(self),
+    };
+    let contents = quote! {
+        match #scrutinee {
+            #(#arms,)*
+        }
+    };
 
-            let variant_field_names: Vec<String> = variant.fields.as_generated_ident("__");
-
-            let variant_arm = match variant_field_names.len() {
-                // qualified variant name => qualified function call()
-                0 => format!("{} => {}()", full_variant_name, full_variant_handler_fn),
-                // qualified variant name (inner params) => qualified function call(inner params forwarded)
-                _ => format!(
-                    "{}({}) => {}({})",
-                    full_variant_name,
-                    variant_field_names.join(","),
-                    full_variant_handler_fn,
-                    variant_field_names.join(",")
-                ),
-            };
+    let (trait_args, assoc_item) = match &kind {
+        ExecuteKind::With(arg_ty) | ExecuteKind::WithMut(arg_ty) => (quote!(<#arg_ty>), quote!()),
+        ExecuteKind::Collect(output_ty) => (quote!(), quote!(type Output = #output_ty;)),
+        ExecuteKind::Try(error_ty) => (quote!(), quote!(type Error = #error_ty;)),
+        _ => (quote!(), quote!()),
+    };
+    let signature = |vis: TokenStream, method: &Ident| match &kind {
+        ExecuteKind::Void => quote!(#vis fn #method(self)),
+        ExecuteKind::With(arg_ty) => quote!(#vis fn #method(self, arg: &#arg_ty)),
+        ExecuteKind::WithMut(arg_ty) => quote!(#vis fn #method(self, arg: &mut #arg_ty)),
+        ExecuteKind::Collect(output_ty) => quote!(#vis fn #method(self) -> #output_ty),
+        ExecuteKind::Try(error_ty) => quote!(#vis fn #method(self) -> Result<(), #error_ty>),
+        // only handlers marked `with_index` use the index
+        ExecuteKind::Indexed => {
+            quote!(#[allow(unused_variables)] #vis fn #method(self, index: usize))
+        }
+        ExecuteKind::InPlace => quote!(#vis fn #method(&mut self)),
+        ExecuteKind::Ref => quote!(#vis fn #method(&self)),
+    };
+    let trait_signature = signature(quote!(), &method);
 
-            variant_arm
-        })
-        .collect();
+    // with a dispatch function, the trait method forwards its arguments to it
+    let (contents, dispatch_impl) = match emit_dispatch_fn {
+        true => {
+            let dispatch_signature =
+                signature(quote!(pub), &Ident::new("dispatch", Span::call_site()));
+            let forwarded_arg = match (kind.has_arg(), kind.has_index()) {
+                (true, _) => quote!(arg),
+                (_, true) => quote!(index),
+                _ => quote!(),
+            };
 
-    let contents =
-        syn::parse_str::<ExprMatch>(&format!("match self {{\n{}\n}}", arms.join(",\n"))).unwrap();
+            (
+                quote!(Self::dispatch(self, #forwarded_arg)),
+                quote! {
+                    #(#impl_attrs)*
+                    #[automatically_derived]
+                    #[allow(clippy::all)]
+                    impl #impl_generics #enum_ident #ty_generics #where_clause {
+                        /// Dispatches this instance to the handlers of its variant.
+                        #dispatch_signature {
+                            #contents
+                        }
+                    }
+                },
+            )
+        }
+        false => (contents, quote!()),
+    };
 
-    let res = quote! {
+    Ok(quote! {
+        #(#impl_attrs)*
         #[automatically_derived]
-        impl Execute for #enum_ident {
-            fn execute(self) {
+        #[allow(clippy::all)]
+        impl #impl_generics #trait_path #trait_args for #enum_ident #ty_generics #where_clause {
+            #assoc_item
+
+            #trait_signature {
                 #contents
             }
         }
+
+        #dispatch_impl
+    })
+}
+
+/// Expands the wildcard match arm that dispatches every variant without a handler to the catch all handler.
+/// The arm may be unreachable when every variant has a handler, e.g. while waiting for new variants.
+fn expand_catch_all_arm(
+    enum_ident: &Ident,
+    handler: &Handler,
+    kind: &ExecuteKind,
+    handlers_in: Option<&Path>,
+) -> Result<TokenStream> {
+    if handler.with_index && !kind.has_index() {
+        return Err(Error::new(
+            handler.span(),
+            "`with_index` handlers require the enum level #[execute_indexed]",
+        ));
+    }
+
+    let mut handler_args = Vec::new();
+    if handler.with_index {
+        handler_args.push(quote!(index));
+    }
+    if kind.has_arg() {
+        handler_args.push(quote!(arg));
+    }
+    let call = handler.call(enum_ident, handlers_in, &handler_args);
+
+    Ok(quote!(#[allow(unreachable_patterns)] _ => #call))
+}
+
+/// Expands the match arm that dispatches a single variant to its handler(s).
+fn expand_arm(
+    enum_ident: &Ident,
+    variant: &Variant,
+    kind: &ExecuteKind,
+    fallback: Option<&Handler>,
+    handlers_in: Option<&Path>,
+) -> Result<TokenStream> {
+    let variant_ident = &variant.ident;
+    let cfg_attrs = cfg_attrs(variant);
+    let variant_handlers_all: Vec<Attribute> = variant.attrs.clone().of_relevant_type("handler");
+
+    // skipped variants do nothing, so they can ignore their fields entirely
+    if let Some(skip) = variant.attrs.clone().single_of_relevant_type("skip")? {
+        if !variant_handlers_all.is_empty() {
+            return Err(Error::new_spanned(
+                skip,
+                format!(
+                    "Variant {}::{} can't have both #[skip] and #[handler(...)]",
+                    enum_ident, variant_ident
+                ),
+            ));
+        }
+
+        let pattern = wildcard_pattern(variant);
+        let body = match kind {
+            ExecuteKind::Try(_) => quote!(Ok(())),
+            ExecuteKind::Collect(_) => {
+                return Err(Error::new_spanned(
+                    skip,
+                    "#[skip] can't be used with #[execute_collect(...)], since every variant must produce an output",
+                ))
+            }
+            _ => quote!({}),
+        };
+
+        return Ok(quote!(#(#cfg_attrs)* #pattern => #body));
+    }
+
+    // error handling for handler attributes, falling back to the prefixed or default handler (if any)
+    let parsed_handlers: Vec<Handler>;
+    let handlers: Vec<&Handler> = match (variant_handlers_all.is_empty(), fallback) {
+        (true, Some(fallback)) => vec![fallback],
+        (true, None) => {
+            return Err(Error::new(
+                variant.span(),
+                format!(
+                    "Variant {}::{} is missing attribute #[handler(your_handler_function)]",
+                    enum_ident, variant_ident
+                ),
+            ))
+        }
+        (false, _) => {
+            parsed_handlers = variant_handlers_all
+                .iter()
+                .map(|attr| attr.parse_args::<Handler>())
+                .collect::<Result<Vec<Handler>>>()?;
+            parsed_handlers.iter().collect()
+        }
     };
+    if let Some(handler) = handlers.iter().find(|handler| handler.with_index) {
+        if !kind.has_index() {
+            return Err(Error::new(
+                handler.span(),
+                "`with_index` handlers require the enum level #[execute_indexed]",
+            ));
+        }
+    }
+    if let Some(handler) = handlers.iter().find(|handler| handler.by_mut) {
+        if !kind.is_in_place() {
+            return Err(Error::new(
+                handler.span(),
+                "`mut` handlers require the enum level #[execute_in_place]",
+            ));
+        }
+    }
+    let field_idents: Vec<Ident> = variant.fields.as_generated_ident("__");
 
-    res
+    // each handler receives the inner params forwarded, followed by the arg (if any).
+    // by-value handlers before the last handler receive clones, so that the last one can take ownership.
+    // in place, the fields are `&mut` bindings that are reborrowed, and by-value handlers always receive clones.
+    // by reference, the fields are `&` bindings that every handler receives as is.
+    let calls: Vec<TokenStream> = handlers
+        .iter()
+        .enumerate()
+        .map(|(i, handler)| {
+            let mut handler_args: Vec<TokenStream> = handler
+                .forwarded_fields(&field_idents)?
+                .into_iter()
+                .map(|(ident, mode)| {
+                    let is_last = i + 1 == handlers.len();
+                    // an explicit mode for the field takes precedence over the handler's
+                    let (by_ref, by_mut) = match mode {
+                        Some(mode) => (mode == FieldMode::Ref, false),
+                        None => (handler.by_ref, handler.by_mut),
+                    };
+
+                    match (kind, by_ref, by_mut) {
+                        // the fields are already shared references
+                        (ExecuteKind::Ref, false, _) if mode.is_some() => quote!((*#ident).clone()),
+                        (ExecuteKind::Ref, _, _) => quote!(#ident),
+                        (ExecuteKind::InPlace, true, _) => quote!(&*#ident),
+                        (ExecuteKind::InPlace, _, true) => quote!(&mut *#ident),
+                        (ExecuteKind::InPlace, _, _) => quote!((*#ident).clone()),
+                        (_, true, _) => quote!(&#ident),
+                        (_, _, _) if !is_last => quote!(#ident.clone()),
+                        (_, _, _) => quote!(#ident),
+                    }
+                })
+                .collect();
+            if handler.with_index {
+                handler_args.push(quote!(index));
+            }
+            if kind.has_arg() {
+                handler_args.push(quote!(arg));
+            }
+
+            Ok(handler.call(variant_ident, handlers_in, &handler_args))
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+    let (last_call, init_calls) = calls.split_last().unwrap();
+
+    // fallible handlers stop at the first failure, leaving the last result as the arm's result
+    let init_calls: Vec<TokenStream> = match kind.is_fallible() {
+        true => init_calls.iter().map(|call| quote!(#call?)).collect(),
+        false => init_calls.to_vec(),
+    };
+
+    let pattern = match variant.fields {
+        Fields::Unit => quote!(Self::#variant_ident),
+        Fields::Unnamed(_) => quote!(Self::#variant_ident(#(#field_idents),*)),
+        Fields::Named(_) => quote!(Self::#variant_ident { #(#field_idents),* }),
+    };
+
+    // inline blocks needn't use every field, and are braced even when they're a single expression
+    let lint_attrs = match handlers
+        .iter()
+        .any(|handler| matches!(handler.target, HandlerTarget::Block(_)))
+    {
+        true => quote!(#[allow(unused_variables, unused_braces)]),
+        false => quote!(),
+    };
+
+    Ok(quote! {
+        #(#cfg_attrs)*
+        #lint_attrs
+        #pattern => {
+            #(#init_calls;)*
+            #last_call
+        }
+    })
 }
@@ -0,0 +1,50 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Expr, Result};
+
+use crate::util::{
+    borrowed_self, cfg_attrs, enum_variants, wildcard_pattern, SingleOfRelevantType,
+};
+
+/// Expands the [derive(Prioritized)] macro into a `Prioritized` implementation using #[priority(10)] helper attributes.
+/// The priority may be any `i32` expression, e.g. `-5` or `MY_PRIORITY`, and variants without a #[priority(...)] have priority `0`.
+/// Example:
+/// ```ignore
+/// #[derive(Prioritized)]
+/// pub enum Test {
+///     #[priority(10)]
+///     Init,
+///     Run(f32),
+/// }
+/// ```
+pub fn expand_prioritized(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+
+    let arms = variants
+        .iter()
+        .map(|variant| {
+            let cfg_attrs = cfg_attrs(variant);
+            let pattern = wildcard_pattern(variant);
+            let priority = match variant.attrs.clone().single_of_relevant_type("priority")? {
+                Some(attr) => attr.parse_args::<Expr>()?,
+                None => syn::parse_quote!(0),
+            };
+
+            Ok(quote!(#(#cfg_attrs)* #pattern => #priority))
+        })
+        .collect::<Result<Vec<TokenStream>>>()?;
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics Prioritized for #enum_ident #ty_generics #where_clause {
+            fn priority(&self) -> i32 {
+                match #scrutinee {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
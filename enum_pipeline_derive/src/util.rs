@@ -1,4 +1,9 @@
-use syn::{Attribute, Fields};
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::{
+    punctuated::Punctuated, spanned::Spanned, token::Comma, Attribute, Data, Error, Fields, Ident,
+    Result, Variant,
+};
 
 pub trait OfRelevantType<T> {
     fn of_relevant_type(self, ty: &str) -> T;
@@ -7,25 +12,159 @@ pub trait OfRelevantType<T> {
 impl OfRelevantType<Vec<Attribute>> for Vec<Attribute> {
     fn of_relevant_type(self, ty: &str) -> Vec<Attribute> {
         self.into_iter()
-            .filter_map(
-                |attr| match attr.path.get_ident().map_or(false, |p| p == ty) {
-                    true => Some(attr),
-                    _ => None,
-                },
-            )
+            .filter(|attr| attr.path.is_ident(ty))
             .collect()
     }
 }
 
+pub trait SingleOfRelevantType<T> {
+    fn single_of_relevant_type(self, ty: &str) -> Result<Option<T>>;
+}
+
+/// Like `OfRelevantType`, but for attributes that may be given at most once.
+impl SingleOfRelevantType<Attribute> for Vec<Attribute> {
+    fn single_of_relevant_type(self, ty: &str) -> Result<Option<Attribute>> {
+        let mut attrs = self.of_relevant_type(ty).into_iter();
+
+        match (attrs.next(), attrs.next()) {
+            (_, Some(extra)) => Err(Error::new_spanned(
+                extra,
+                format!("Only one #[{}(...)] attribute is allowed", ty),
+            )),
+            (attr, None) => Ok(attr),
+        }
+    }
+}
+
 pub trait AsGeneratedIdent<T> {
-    fn as_generated_ident(self, prefix: &str) -> Vec<T>;
+    fn as_generated_ident(&self, prefix: &str) -> Vec<T>;
 }
 
-impl AsGeneratedIdent<String> for Fields {
-    fn as_generated_ident(self, prefix: &str) -> Vec<String> {
-        self.into_iter()
+/// Named fields keep their declared names, while unnamed fields are given `prefix` + position idents.
+/// Generated idents are spanned at their field, so that errors about a forwarded field point at it.
+impl AsGeneratedIdent<Ident> for Fields {
+    fn as_generated_ident(&self, prefix: &str) -> Vec<Ident> {
+        self.iter()
             .enumerate()
-            .map(|(i, _)| format!("{}{}", prefix, i + 1))
+            .map(|(i, field)| match &field.ident {
+                Some(ident) => ident.clone(),
+                None => format_ident!("{}{}", prefix, i + 1, span = field.span()),
+            })
             .collect()
     }
 }
+
+/// The variants of the derive input, or an error spanning `ident` if the input isn't an enum.
+pub fn enum_variants(data: Data, ident: &Ident) -> Result<Punctuated<Variant, Comma>> {
+    match data {
+        Data::Enum(e) => Ok(e.variants),
+        _ => Err(Error::new(
+            ident.span(),
+            "Pipeline derive macro only works on enums",
+        )),
+    }
+}
+
+/// The #[cfg(...)] attributes of `variant`, to be repeated on anything generated for it so that
+/// the generated code is compiled out along with the variant.
+pub fn cfg_attrs(variant: &Variant) -> Vec<Attribute> {
+    variant.attrs.clone().of_relevant_type("cfg")
+}
+
+/// A pattern matching `variant` regardless of its fields, e.g. `Self::Run(..)`.
+pub fn wildcard_pattern(variant: &Variant) -> TokenStream {
+    let variant_ident = &variant.ident;
+
+    match variant.fields {
+        Fields::Unit => quote!(Self::#variant_ident),
+        Fields::Unnamed(_) => quote!(Self::#variant_ident(..)),
+        Fields::Named(_) => quote!(Self::#variant_ident { .. }),
+    }
+}
+
+/// The value matched on by a method borrowing `self`. Enums without variants match on `*self` instead, since
+/// an empty match is only exhaustive for the uninhabited enum itself, rather than a reference to it.
+pub fn borrowed_self(variants: &Punctuated<Variant, Comma>) -> TokenStream {
+    match variants.is_empty() {
+        true => quote!(*self),
+        false => quote!(self),
+    }
+}
+
+/// Converts a `CamelCase` variant name into `snake_case`, e.g. `LoadHTTPConfig` into `load_http_config`.
+pub fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::with_capacity(name.len() + 4);
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i > 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+
+            if prev.is_lowercase()
+                || prev.is_ascii_digit()
+                || (prev.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+
+    snake
+}
+
+/// The identifier of a generated function named `name`, which is raw when `name` is a keyword, e.g. `r#move`.
+/// Fails for the keywords that can't be raw identifiers, i.e. `self`, `super` and `crate`.
+pub fn function_ident(name: &str, span: Span) -> Result<Ident> {
+    match (name, syn::parse_str::<Ident>(name).is_ok()) {
+        ("self" | "super" | "crate", _) => Err(Error::new(
+            span,
+            format!("`{}` can't be used as the name of a function", name),
+        )),
+        (_, true) => Ok(Ident::new(name, span)),
+        (_, false) => Ok(Ident::new_raw(name, span)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{function_ident, to_snake_case};
+    use proc_macro2::Span;
+
+    #[test]
+    fn snake_case_of_pascal_case() {
+        assert_eq!("allocate_buffer", to_snake_case("AllocateBuffer"));
+        assert_eq!("run", to_snake_case("Run"));
+    }
+
+    #[test]
+    fn snake_case_of_camel_case() {
+        assert_eq!("allocate_buffer", to_snake_case("allocateBuffer"));
+        assert_eq!("already_snake", to_snake_case("already_snake"));
+    }
+
+    #[test]
+    fn snake_case_of_acronyms() {
+        assert_eq!("http_server", to_snake_case("HTTPServer"));
+        assert_eq!("load_http_config", to_snake_case("LoadHTTPConfig"));
+        assert_eq!("gpu", to_snake_case("GPU"));
+    }
+
+    #[test]
+    fn snake_case_of_digit_boundaries() {
+        assert_eq!("step2", to_snake_case("Step2"));
+        assert_eq!("http2_server", to_snake_case("Http2Server"));
+        assert_eq!("vec3_d", to_snake_case("Vec3D"));
+    }
+
+    #[test]
+    fn function_ident_of_keywords() {
+        let ident = |name| function_ident(name, Span::call_site()).unwrap().to_string();
+
+        assert_eq!("run", ident("run"));
+        assert_eq!("r#move", ident("move"));
+        assert_eq!("r#return", ident("return"));
+        assert!(function_ident("super", Span::call_site()).is_err());
+    }
+}
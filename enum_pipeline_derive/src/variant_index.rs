@@ -0,0 +1,43 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{DeriveInput, Result};
+
+use crate::util::{borrowed_self, cfg_attrs, enum_variants, wildcard_pattern};
+
+/// Expands the [derive(VariantIndex)] macro into a `VariantIndex` implementation, numbering the variants from `0`
+/// in declaration order. Variants removed by #[cfg(...)] keep their number, so indices don't change between builds.
+/// Example:
+/// ```ignore
+/// #[derive(VariantIndex)]
+/// pub enum Test {
+///     Allocate(f32, f32),
+///     Run(f32),
+/// }
+/// ```
+pub fn expand_variant_index(input: DeriveInput) -> Result<TokenStream> {
+    let enum_ident = input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let variants = enum_variants(input.data, &enum_ident)?;
+    let scrutinee = borrowed_self(&variants);
+    let variant_count = variants.len();
+
+    let arms = variants.iter().enumerate().map(|(index, variant)| {
+        let cfg_attrs = cfg_attrs(variant);
+        let pattern = wildcard_pattern(variant);
+
+        quote!(#(#cfg_attrs)* #pattern => #index)
+    });
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics VariantIndex for #enum_ident #ty_generics #where_clause {
+            const VARIANT_COUNT: usize = #variant_count;
+
+            fn variant_index(&self) -> usize {
+                match #scrutinee {
+                    #(#arms,)*
+                }
+            }
+        }
+    })
+}
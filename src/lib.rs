@@ -1,4 +1,24 @@
-use std::marker::PhantomData;
+// the tests use the standard library even without the `std` feature
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+// the derives refer to items like `::enum_pipeline::__private::String`, which this resolves within the crate too
+extern crate self as enum_pipeline;
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+use core::iter::FromIterator;
+use core::marker::PhantomData;
+use core::sync::atomic::{AtomicBool, Ordering};
+#[cfg(feature = "std")]
+use std::any::Any;
+#[cfg(feature = "std")]
+use std::panic::{self, UnwindSafe};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
 /// Provides an execute handler for pipelines.
 pub trait Execute {
@@ -21,271 +41,2328 @@ pub trait ExecuteWithMut<TArg> {
     fn execute(self, arg: &mut TArg);
 }
 
-/// A pipeline vector which represents a series of `Execute`-able operations.
-pub struct PipelineVec<T> {
-    /// The ordered step of operations.
-    steps: Vec<T>,
+/// Provides a fallible execute handler for pipelines, with a argument of type `TArg`.
+pub trait TryExecuteWith<TArg> {
+    /// The error produced when execution fails.
+    type Error;
+
+    /// Execute a pipeline call to this instance with an argument, stopping at the first failure.
+    /// Responsible for invoking the relevant handler(s).
+    fn try_execute(self, arg: &TArg) -> Result<(), Self::Error>;
+}
+
+/// Provides an execute handler for pipelines, with an argument of type `TArg1` and a mutable argument of type `TArg2`.
+pub trait ExecuteWith2<TArg1, TArg2> {
+    /// Execute a pipeline call to this instance with a read-only and a mutable argument.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute(self, a1: &TArg1, a2: &mut TArg2);
+}
+
+/// Provides an execute handler for pipelines, which is told the position of each step.
+pub trait ExecuteIndexed {
+    /// Execute a pipeline call to this instance, as the step at the zero-based `index` of its pipeline.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_indexed(self, index: usize);
+}
+
+/// Provides an execute handler for pipelines that updates each step in place, rather than consuming it,
+/// so that steps can keep state across executions. For types that are also `Execute`, method call syntax
+/// prefers the consuming `Execute::execute`, so call this as `ExecuteInPlace::execute(&mut step)`.
+pub trait ExecuteInPlace {
+    /// Execute a pipeline call to this instance, which may mutate it.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute(&mut self);
 }
 
-/// `Execute`-ing to a `PipelineVec<T>` executing the `steps` in order.
-impl<T> Execute for PipelineVec<T>
+/// Provides an object-safe execute handler for pipelines, so that steps of different types can be executed
+/// as `Box<dyn BoxExecute>`. Every `Execute`-able type is `BoxExecute`-able.
+pub trait BoxExecute {
+    /// Execute a pipeline call to this boxed instance.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_boxed(self: Box<Self>);
+}
+
+impl<T> BoxExecute for T
 where
     T: Execute,
 {
+    fn execute_boxed(self: Box<Self>) {
+        (*self).execute()
+    }
+}
+
+/// `Execute`-ing a boxed `BoxExecute` step executes the step, which allows a `PipelineVec<Box<dyn BoxExecute>>`
+/// to mix steps of different types.
+impl<'a> Execute for Box<dyn BoxExecute + 'a> {
     fn execute(self) {
-        for step in self.steps {
-            step.execute()
-        }
+        <dyn BoxExecute as BoxExecute>::execute_boxed(self)
     }
 }
 
-/// A pipeline vector which represents a series of `ExecuteWith`-able operations with an argument of type `TArg`.
-pub struct PipelineVecWith<T, TArg> {
-    /// The ordered step of operations.
-    steps: Vec<T>,
+/// Like `Box<dyn BoxExecute>`, for steps that can be sent between threads.
+impl<'a> Execute for Box<dyn BoxExecute + Send + 'a> {
+    fn execute(self) {
+        <dyn BoxExecute + Send as BoxExecute>::execute_boxed(self)
+    }
+}
 
-    /// Phantom data to remember the argument type with.
-    arg_type: PhantomData<TArg>,
+/// Provides a non-consuming execute handler for pipelines.
+pub trait ExecuteRef {
+    /// Execute a pipeline call to this instance, without consuming it.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_ref(&self);
 }
 
-/// `Execute`-ing to a `PipelineVecWith<T, TArg>` executes the `steps` in order, passing `arg` along.
-impl<T, TArg> ExecuteWith<TArg> for PipelineVecWith<T, TArg>
-where
-    T: ExecuteWith<TArg>,
-{
-    fn execute(self, arg: &TArg) {
-        for step in self.steps {
-            step.execute(arg)
-        }
-    }
+/// Provides a fallible execute handler for pipelines.
+pub trait TryExecute {
+    /// The error produced when execution fails.
+    type Error;
+
+    /// Execute a pipeline call to this instance, stopping at the first failure.
+    /// Responsible for invoking the relevant handler(s).
+    fn try_execute(self) -> Result<(), Self::Error>;
 }
 
-/// `Execute`-ing to a `PipelineVecWith<T, TArg>` executes the `steps` in order, passing a mutable `arg` along.
-impl<T, TArg> ExecuteWithMut<TArg> for PipelineVecWith<T, TArg>
-where
-    T: ExecuteWithMut<TArg>,
-{
-    fn execute(self, arg: &mut TArg) {
-        for step in self.steps {
-            step.execute(arg)
-        }
-    }
+/// Provides an execute handler for pipelines that produces an output.
+pub trait ExecuteCollect {
+    /// The output produced by execution.
+    type Output;
+
+    /// Execute a pipeline call to this instance, returning its output.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_collect(self) -> Self::Output;
 }
 
-/// Provides a way to convert into a `PipelineVec` for ordered execution.
-pub trait IntoPipelineVec<T>
-where
-    T: Execute,
-{
-    /// Creates a `PipelineVec` that can be executed, consuming the source.
-    fn into_pipeline(self) -> PipelineVec<T>;
+/// Provides an execute handler for pipelines that reads a shared context of type `Ctx`, updates an accumulator
+/// of type `Acc` and produces an output, e.g. a game loop stage reading the config, updating the world and
+/// returning events.
+pub trait ExecuteScan<Ctx, Acc> {
+    /// The output produced by execution.
+    type Output;
+
+    /// Execute a pipeline call to this instance with the context and accumulator, returning its output.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_scan(self, ctx: &Ctx, acc: &mut Acc) -> Self::Output;
 }
 
-/// Provides a way to convert into a `PipelineVecWith` for ordered execution with an argument of type `TArg`.
-pub trait IntoPipelineVecWith<T, TArg>
-where
-    T: ExecuteWith<TArg>,
-{
-    /// Creates a `PipelineVecWith` that can be executed with an argument, consuming the source.
-    fn into_pipeline(self) -> PipelineVecWith<T, TArg>;
+/// Provides a human-readable description of a pipeline step, e.g. for a dry-run of a pipeline.
+pub trait Describe {
+    /// Describes what executing this instance would do, without executing it.
+    fn describe(&self) -> String;
 }
 
-/// Provides a way to convert into a `PipelineVecWith` for ordered execution with a mutable argument of type `TArg`.
-pub trait IntoPipelineVecWithMut<T, TArg>
-where
-    T: ExecuteWithMut<TArg>,
-{
-    /// Creates a `PipelineVecWith` that can be executed with a mutable argument, consuming the source.
-    fn into_pipeline(self) -> PipelineVecWith<T, TArg>;
+/// Provides a human-friendly label for a pipeline step, e.g. for logging and metrics.
+pub trait Labeled {
+    /// The label of this instance, which may differ from its variant name.
+    fn label(&self) -> &'static str;
 }
 
-/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVec` for execution.
-impl<T> IntoPipelineVec<T> for Vec<T>
-where
-    T: Execute,
-{
-    /// Creates a `PipelineVec` that can be executed, consuming the source `Vec`.
-    fn into_pipeline(self) -> PipelineVec<T> {
-        PipelineVec { steps: self }
-    }
+/// Provides the phase of a pipeline step, for organizing a large pipeline into named phases like "setup" and "teardown".
+pub trait Phased {
+    /// The phase of this instance, if it's in one.
+    fn phase(&self) -> Option<&'static str>;
 }
 
-/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVecWith` for ordered execution with an argument of type `TArg`.
-impl<T, TArg> IntoPipelineVecWith<T, TArg> for Vec<T>
-where
-    T: ExecuteWith<TArg>,
-{
-    /// Creates a `PipelineVecWith` that can be executed with an argument, consuming the source `Vec`.
-    fn into_pipeline(self) -> PipelineVecWith<T, TArg> {
-        PipelineVecWith {
-            steps: self,
-            arg_type: PhantomData,
-        }
-    }
+/// Provides the declared dependencies of a pipeline step, for executing steps in dependency order.
+pub trait HasDeps {
+    /// The name of this instance's variant, which other steps may execute after.
+    fn variant_name(&self) -> &'static str;
+
+    /// The names of the variants this instance executes after.
+    fn after(&self) -> &'static [&'static str];
 }
 
-/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVecWithMut` for ordered execution with an argument of type `TArg`.
-impl<T, TArg> IntoPipelineVecWithMut<T, TArg> for Vec<T>
-where
-    T: ExecuteWithMut<TArg>,
-{
-    /// Creates a `PipelineVecWith` that can be executed with a mutable argument, consuming the source `Vec`.
-    fn into_pipeline(self) -> PipelineVecWith<T, TArg> {
-        PipelineVecWith {
-            steps: self,
-            arg_type: PhantomData,
-        }
-    }
+/// Provides the priority of a pipeline step, for executing steps that were assembled out of order.
+pub trait Prioritized {
+    /// The priority of this instance, where higher priorities execute first.
+    fn priority(&self) -> i32;
 }
 
-#[cfg(test)]
-mod readme_test {
-    use crate::{Execute, IntoPipelineVec};
+/// Provides the position of a pipeline step's variant, e.g. for bucketing metrics per variant.
+pub trait VariantIndex {
+    /// The number of variants, so that every `variant_index()` is below it.
+    const VARIANT_COUNT: usize;
 
-    enum Operations {
-        Allocate(f32, f32),
-        Init,
-        Run(f32),
-    }
+    /// The zero-based index of this instance's variant, in declaration order.
+    fn variant_index(&self) -> usize;
+}
 
-    impl Execute for Operations {
-        fn execute(self) {
-            match self {
-                Operations::Allocate(_x, _y) => println!("allocate something"),
-                Operations::Init => println!("init"),
-                Operations::Run(_delta) => println!("do work"),
-            }
-        }
-    }
+/// Provides validation of a pipeline step before it is built into a pipeline.
+pub trait Validate {
+    /// Checks that this instance is well formed, describing the problem if it isn't.
+    fn validate(&self) -> Result<(), String>;
+}
 
-    #[test]
-    fn do_work() {
-        let my_op_pipeline = vec![
-            Operations::Init,
-            Operations::Allocate(1.0, 1.0),
-            Operations::Init,
-            Operations::Run(1.0),
-        ]
-        .into_pipeline();
+/// Provides a two phase execute handler for transactional pipelines, where every step of a pipeline is prepared
+/// before any step commits its side effects.
+pub trait TwoPhase {
+    /// Checks that this instance can commit, describing the problem if it can't. Shouldn't have side effects.
+    fn prepare(&self) -> Result<(), String>;
 
-        my_op_pipeline.execute();
-        // prints:
-        // init
-        // allocate something
-        // init
-        // do work
-    }
+    /// Commits this instance, once every step of the pipeline has been prepared.
+    fn commit(self);
 }
 
-#[cfg(test)]
-mod tests {
-    use crate::{
-        Execute, ExecuteWith, ExecuteWithMut, IntoPipelineVec, IntoPipelineVecWith,
-        IntoPipelineVecWithMut,
-    };
-    use enum_pipeline_derive::Execute;
+/// Decides how each step of a pipeline is dispatched, e.g. inline, on a thread pool or with logging,
+/// without changing the call sites that execute the pipeline.
+pub trait ExecutionStrategy {
+    /// Executes a single pipeline step.
+    fn run<T: Execute>(&self, step: T);
+}
 
-    #[derive(Execute)]
-    enum VoidDispatchPipeline {
-        #[handler(VoidDispatchPipeline::handle_one)]
-        One,
-        #[handler(handle_two)]
-        Two,
+/// The default `ExecutionStrategy`, which executes each step immediately on the calling thread.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct InlineStrategy;
+
+impl ExecutionStrategy for InlineStrategy {
+    fn run<T: Execute>(&self, step: T) {
+        step.execute()
     }
+}
 
-    static mut VOID_ONE_COUNT: i32 = 0;
-    static mut VOID_TWO_COUNT: i32 = 0;
+/// The boxed future returned by `ExecuteAsync::execute_async`.
+#[cfg(feature = "async")]
+pub type ExecuteFuture = core::pin::Pin<Box<dyn core::future::Future<Output = ()> + Send>>;
 
-    impl VoidDispatchPipeline {
-        fn handle_one() {
-            unsafe {
-                VOID_ONE_COUNT += 1;
-            }
-        }
+/// Provides an asynchronous execute handler for pipelines.
+#[cfg(feature = "async")]
+pub trait ExecuteAsync {
+    /// Execute a pipeline call to this instance, completing when the returned future does.
+    /// Responsible for invoking the relevant handler(s).
+    fn execute_async(self) -> ExecuteFuture;
+}
 
-        fn handle_two() {
-            unsafe {
-                VOID_TWO_COUNT += 1;
-            }
-        }
-    }
+mod sealed {
+    /// Implemented only by the pipeline containers of this crate, which keeps `Pipeline` from being implemented elsewhere.
+    pub trait Sealed {}
+}
 
-    #[test]
-    fn void_dispatch_works() {
-        let pipeline = vec![VoidDispatchPipeline::One, VoidDispatchPipeline::Two].into_pipeline();
+/// Implemented by the pipeline containers of this crate, i.e. `PipelineVec`, `PipelineVecWith`, `PipelineVecWith2`,
+/// `PipelineDeque`, `PipelineSlice` and `PipelineArray`, with `Step` as the type of their steps.
+///
+/// Only this trait is sealed, so it can't be implemented outside of this crate. The pipeline traits like `Execute` stay
+/// open for user types, while the orphan rules already keep other crates from implementing them for the containers,
+/// e.g. for `PipelineVec<MyStep>`. Libraries building on this crate can bound on `P: Pipeline` to accept only these
+/// containers, without a type from elsewhere being able to opt in and break assumptions made about them.
+pub trait Pipeline: sealed::Sealed {
+    /// The type of the steps of the pipeline.
+    type Step;
+}
 
-        pipeline.execute();
+impl<T, C> sealed::Sealed for PipelineSeq<T, C> {}
 
-        unsafe {
-            assert_eq!(1, VOID_ONE_COUNT);
-            assert_eq!(1, VOID_TWO_COUNT);
-        }
-    }
+impl<T, C> Pipeline for PipelineSeq<T, C> {
+    type Step = T;
+}
 
-    enum RefDataPipeline {
-        One(f32),
-        Two,
-    }
+impl<T> sealed::Sealed for PipelineDeque<T> {}
 
-    static mut REF_ONE_VALUE: f32 = 0.0;
-    static mut REF_TWO_COUNT: i32 = 0;
+impl<T> Pipeline for PipelineDeque<T> {
+    type Step = T;
+}
 
-    struct RefDataPipelineData {
-        mult: f32,
+impl<'a, T> sealed::Sealed for PipelineSlice<'a, T> {}
+
+impl<'a, T> Pipeline for PipelineSlice<'a, T> {
+    type Step = T;
+}
+
+impl<T, TArg> sealed::Sealed for PipelineVecWith<T, TArg> {}
+
+impl<T, TArg> Pipeline for PipelineVecWith<T, TArg> {
+    type Step = T;
+}
+
+impl<T, TArg1, TArg2> sealed::Sealed for PipelineVecWith2<T, TArg1, TArg2> {}
+
+impl<T, TArg1, TArg2> Pipeline for PipelineVecWith2<T, TArg1, TArg2> {
+    type Step = T;
+}
+
+impl<T, const N: usize> sealed::Sealed for PipelineArray<T, N> {}
+
+impl<T, const N: usize> Pipeline for PipelineArray<T, N> {
+    type Step = T;
+}
+
+/// A pipeline which represents a series of `Execute`-able operations, stored in a container of type `C`.
+/// Most pipelines are `PipelineVec`s, which store their steps in a `Vec`, but other containers can be used too,
+/// e.g. a `SmallVec` to keep short pipelines off the heap. Which methods are available depends on the container:
+/// executing needs `C: IntoIterator<Item = T>`, inspecting the steps needs `C: AsRef<[T]>`, and pushing needs
+/// `C: Extend<T>`, while the rest of the editing methods are specific to `PipelineVec`.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PipelineSeq<T, C = Vec<T>> {
+    /// The ordered step of operations.
+    steps: C,
+
+    /// Phantom data to remember the step type with.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    step_type: PhantomData<T>,
+}
+
+/// A pipeline vector which represents a series of `Execute`-able operations.
+pub type PipelineVec<T> = PipelineSeq<T, Vec<T>>;
+
+/// A pipeline storing its steps in a `SmallVec`, which keeps up to the array size of `A` steps inline.
+#[cfg(feature = "smallvec")]
+pub type PipelineSmallVec<A> = PipelineSeq<<A as smallvec::Array>::Item, smallvec::SmallVec<A>>;
+
+/// Pipelines are printed as `PipelineVec { .. }` when stored in a `Vec`, as they were before other containers
+/// were supported, and as `PipelineSeq { .. }` otherwise.
+impl<T, C: fmt::Debug> fmt::Debug for PipelineSeq<T, C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match core::any::type_name::<C>() == core::any::type_name::<Vec<T>>() {
+            true => "PipelineVec",
+            false => "PipelineSeq",
+        };
+
+        f.debug_struct(name).field("steps", &self.steps).finish()
     }
+}
 
-    impl RefDataPipeline {
-        fn handle_one(v: f32, arg: &RefDataPipelineData) {
-            unsafe {
-                REF_ONE_VALUE += v * arg.mult;
-            }
+impl<T, C: Clone> Clone for PipelineSeq<T, C> {
+    fn clone(&self) -> Self {
+        PipelineSeq {
+            steps: self.steps.clone(),
+            step_type: PhantomData,
         }
+    }
+}
 
-        fn handle_two(_arg: &RefDataPipelineData) {
-            unsafe {
-                REF_TWO_COUNT += 1;
-            }
+impl<T, C> PipelineSeq<T, C> {
+    /// Creates a pipeline that executes the steps of `steps` in order.
+    pub fn from_steps(steps: C) -> Self {
+        PipelineSeq {
+            steps,
+            step_type: PhantomData,
         }
     }
+}
 
-    impl ExecuteWith<RefDataPipelineData> for RefDataPipeline {
-        fn execute(self, arg: &RefDataPipelineData) {
-            match self {
-                RefDataPipeline::One(f) => RefDataPipeline::handle_one(f, arg),
-                RefDataPipeline::Two => RefDataPipeline::handle_two(arg),
-            }
+impl<T> PipelineVec<T> {
+    /// Creates a pipeline with no steps, which does nothing when executed.
+    pub fn new() -> Self {
+        PipelineVec {
+            steps: Vec::new(),
+            step_type: PhantomData,
         }
     }
 
-    #[test]
-    fn ref_data_pipeline_works() {
-        let pipeline = vec![RefDataPipeline::One(24.0), RefDataPipeline::Two].into_pipeline();
+    /// Creates a pipeline with no steps, with room for at least `capacity` steps before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PipelineVec {
+            steps: Vec::with_capacity(capacity),
+            step_type: PhantomData,
+        }
+    }
 
-        let data = RefDataPipelineData { mult: 2.0 };
+    /// Reserves room for at least `additional` more steps, so that pushing them doesn't reallocate.
+    pub fn reserve(&mut self, additional: usize) {
+        self.steps.reserve(additional)
+    }
 
-        pipeline.execute(&data);
+    /// Inserts a step at `index`, shifting all later steps back, so it executes before the step previously at `index`.
+    /// Panics if `index > len`.
+    pub fn insert(&mut self, index: usize, step: T) {
+        self.steps.insert(index, step)
+    }
 
-        unsafe {
-            assert_eq!(48.0, REF_ONE_VALUE);
-            assert_eq!(1, REF_TWO_COUNT);
-        }
+    /// Removes and returns the step at `index`, shifting all later steps forward.
+    /// Panics if `index` is out of range.
+    pub fn remove(&mut self, index: usize) -> T {
+        self.steps.remove(index)
     }
 
-    enum MutDataPipeline {
-        One(f32),
-        Two,
+    /// Removes all of the steps, e.g. to reuse the pipeline as a buffer.
+    pub fn clear(&mut self) {
+        self.steps.clear()
     }
 
-    #[derive(Default)]
-    struct MutDataPipelineData {
-        one_value: f32,
-        two_count: i32,
+    /// Keeps only the first `len` steps, removing the rest. Has no effect if `len` is at least the number of steps.
+    pub fn truncate(&mut self, len: usize) {
+        self.steps.truncate(len)
     }
 
-    // no macro yet, srry
-    impl ExecuteWithMut<MutDataPipelineData> for MutDataPipeline {
-        fn execute(self, arg: &mut MutDataPipelineData) {
-            match self {
+    /// Keeps only the steps for which `f` returns `true`, preserving the order of the kept steps.
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.steps.retain(f)
+    }
+
+    /// Moves all the steps of `other` to the end of the pipeline, so they execute after the existing steps.
+    pub fn append(&mut self, mut other: PipelineVec<T>) {
+        self.steps.append(&mut other.steps)
+    }
+
+    /// Creates a pipeline that executes the steps of `self` followed by the steps of `other`, consuming both.
+    pub fn concat(mut self, other: PipelineVec<T>) -> PipelineVec<T> {
+        self.append(other);
+        self
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+{
+    /// Returns an iterator over the steps, in execution order.
+    pub fn iter(&self) -> core::slice::Iter<'_, T> {
+        self.steps.as_ref().iter()
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.as_ref().len()
+    }
+
+    /// Returns `true` if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.as_ref().is_empty()
+    }
+
+    /// Returns the step at `index`, or `None` if it is out of range.
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.steps.as_ref().get(index)
+    }
+
+    /// Returns the first step to execute, or `None` if the pipeline is empty.
+    pub fn first(&self) -> Option<&T> {
+        self.steps.as_ref().first()
+    }
+
+    /// Returns the last step to execute, or `None` if the pipeline is empty.
+    pub fn last(&self) -> Option<&T> {
+        self.steps.as_ref().last()
+    }
+
+    /// Checks every pair of steps against `rule`, which is given the earlier and the later step of the pair and
+    /// returns whether they may execute in that order, e.g. to check that setup steps come before the steps using them.
+    /// Returns the indices of the first pair that violates the rule, ordered by the earlier then the later index.
+    pub fn validate_order<F>(&self, rule: F) -> Result<(), (usize, usize)>
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        for (i, earlier) in self.iter().enumerate() {
+            for (j, later) in self.iter().enumerate().skip(i + 1) {
+                if !rule(earlier, later) {
+                    return Err((i, j));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsMut<[T]>,
+{
+    /// Returns an iterator that allows modifying each step, in execution order.
+    pub fn iter_mut(&mut self) -> core::slice::IterMut<'_, T> {
+        self.steps.as_mut().iter_mut()
+    }
+
+    /// Returns the step at `index` mutably, or `None` if it is out of range.
+    pub fn get_mut(&mut self, index: usize) -> Option<&mut T> {
+        self.steps.as_mut().get_mut(index)
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: Extend<T>,
+{
+    /// Appends a step to the end of the pipeline.
+    pub fn push(&mut self, step: T) {
+        self.steps.extend(core::iter::once(step))
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+{
+    /// Splits the steps into a pipeline of those for which `f` returns `true` and a pipeline of the rest, consuming the source.
+    /// Both pipelines keep the relative execution order of their steps.
+    pub fn partition<F>(self, f: F) -> (PipelineVec<T>, PipelineVec<T>)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let (matching, rest): (Vec<T>, Vec<T>) = self.steps.into_iter().partition(f);
+
+        (
+            PipelineVec {
+                steps: matching,
+                step_type: PhantomData,
+            },
+            PipelineVec {
+                steps: rest,
+                step_type: PhantomData,
+            },
+        )
+    }
+
+    /// Creates a pipeline by transforming each step with `f`, e.g. to wrap steps in a decorator, consuming the source.
+    /// The steps keep their execution order.
+    pub fn map_steps<B, F>(self, f: F) -> PipelineVec<B>
+    where
+        F: FnMut(T) -> B,
+    {
+        PipelineVec {
+            steps: self.steps.into_iter().map(f).collect(),
+            step_type: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for PipelineVec<T> {
+    fn default() -> Self {
+        PipelineVec::new()
+    }
+}
+
+impl<T> PipelineVec<T>
+where
+    T: PartialEq,
+{
+    /// Removes consecutive duplicate steps, e.g. left behind when merging pipelines, keeping the first of each run.
+    /// Duplicates that aren't adjacent are kept.
+    pub fn dedup(&mut self) {
+        self.steps.dedup()
+    }
+}
+
+/// Adding two `PipelineVec<T>`s concatenates them, executing the left hand steps first.
+impl<T> core::ops::Add for PipelineVec<T> {
+    type Output = PipelineVec<T>;
+
+    fn add(self, other: PipelineVec<T>) -> PipelineVec<T> {
+        self.concat(other)
+    }
+}
+
+/// Indexing a `PipelineVec<T>` returns the step at that position, panicking if it is out of range.
+impl<T> core::ops::Index<usize> for PipelineVec<T> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.steps[index]
+    }
+}
+
+impl<T> AsRef<[T]> for PipelineVec<T> {
+    fn as_ref(&self) -> &[T] {
+        &self.steps
+    }
+}
+
+/// Dereferencing a `PipelineVec<T>` gives read-only access to its steps as a slice. There is deliberately no
+/// `DerefMut`, so steps are only changed through the pipeline's own methods.
+impl<T> core::ops::Deref for PipelineVec<T> {
+    type Target = [T];
+
+    fn deref(&self) -> &[T] {
+        &self.steps
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute + Send,
+{
+    /// Executes all of the steps concurrently on the rayon thread pool, consuming the pipeline.
+    /// Steps may run in any order, so this is only suitable for steps that don't depend on each other.
+    pub fn execute_parallel(self)
+    where
+        C: rayon::iter::IntoParallelIterator<Item = T>,
+    {
+        use rayon::prelude::*;
+
+        self.steps.into_par_iter().for_each(|step| step.execute())
+    }
+
+    /// Executes the steps in batches of `size` steps, consuming the pipeline.
+    /// Each batch runs concurrently on the rayon thread pool, but a batch only starts once the previous one has completed.
+    /// The last batch may have fewer than `size` steps. Panics if `size` is zero.
+    pub fn execute_chunked(self, size: usize) {
+        use rayon::prelude::*;
+
+        assert!(size != 0, "execute_chunked requires a non-zero chunk size");
+
+        let mut steps = self.steps.into_iter();
+        loop {
+            let chunk: Vec<T> = steps.by_ref().take(size).collect();
+            if chunk.is_empty() {
+                break;
+            }
+
+            chunk.into_par_iter().for_each(|step| step.execute())
+        }
+    }
+}
+
+/// A `PipelineVec` of `PipelineVec`s executes each inner pipeline in order, since `PipelineVec<T>` is itself `Execute`-able.
+impl<T> PipelineVec<PipelineVec<T>> {
+    /// Concatenates the steps of every inner pipeline into one flat pipeline, consuming the source.
+    /// The flat pipeline executes in the same order as the nested one: outer order first, then inner order.
+    pub fn flatten(self) -> PipelineVec<T> {
+        PipelineVec {
+            steps: self
+                .steps
+                .into_iter()
+                .flat_map(|inner| inner.steps)
+                .collect(),
+            step_type: PhantomData,
+        }
+    }
+}
+
+/// Iterates over the steps of a `PipelineVec<T>` in execution order, consuming it.
+impl<T> IntoIterator for PipelineVec<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.into_iter()
+    }
+}
+
+/// Iterates over references to the steps of a `PipelineVec<T>` in execution order.
+impl<'a, T> IntoIterator for &'a PipelineVec<T> {
+    type Item = &'a T;
+    type IntoIter = core::slice::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.iter()
+    }
+}
+
+/// Iterates over mutable references to the steps of a `PipelineVec<T>` in execution order.
+impl<'a, T> IntoIterator for &'a mut PipelineVec<T> {
+    type Item = &'a mut T;
+    type IntoIter = core::slice::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.steps.iter_mut()
+    }
+}
+
+/// Executes a single step of a pipeline.
+/// With the `tracing` feature enabled, the step runs inside a `step` span and emits trace events around it.
+#[cfg(feature = "tracing")]
+fn execute_step<T: Execute>(index: usize, step: T) {
+    let _span = tracing::trace_span!("step", index).entered();
+
+    tracing::trace!("executing step");
+    step.execute();
+    tracing::trace!("executed step");
+}
+
+/// Executes a single step of a pipeline.
+#[cfg(not(feature = "tracing"))]
+#[inline]
+fn execute_step<T: Execute>(_index: usize, step: T) {
+    step.execute()
+}
+
+/// `Execute`-ing to a `PipelineSeq<T, C>` executing the `steps` in order.
+impl<T, C> Execute for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute,
+{
+    fn execute(self) {
+        for (index, step) in self.steps.into_iter().enumerate() {
+            execute_step(index, step)
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute,
+{
+    /// Executes the `steps` in order, consuming the pipeline.
+    /// With the `tracing` feature enabled, execution happens inside a `pipeline` span carrying `name`,
+    /// otherwise this is equivalent to `execute`.
+    pub fn execute_instrumented(self, name: &str) {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::trace_span!("pipeline", name).entered();
+        #[cfg(not(feature = "tracing"))]
+        let _ = name;
+
+        self.execute()
+    }
+
+    /// Executes the `steps` in reverse order, consuming the pipeline.
+    /// Handy for tearing down whatever a forward execution set up.
+    pub fn execute_rev(self)
+    where
+        C::IntoIter: DoubleEndedIterator,
+    {
+        for step in self.steps.into_iter().rev() {
+            step.execute()
+        }
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, calling `before` and `after` with the index of
+    /// each step around its execution. Useful for logging, timing or progress reporting without touching handlers.
+    pub fn execute_with_hooks<F, G>(self, mut before: F, mut after: G)
+    where
+        F: FnMut(usize),
+        G: FnMut(usize),
+    {
+        for (index, step) in self.steps.into_iter().enumerate() {
+            before(index);
+            step.execute();
+            after(index);
+        }
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, handing each step to `strategy` to run.
+    pub fn execute_with_strategy<S>(self, strategy: &S)
+    where
+        S: ExecutionStrategy,
+    {
+        for step in self.steps {
+            strategy.run(step)
+        }
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, and returns how long each step took to execute.
+    /// The durations are in step order, so the slowest stages of a pipeline are easy to spot.
+    #[cfg(feature = "std")]
+    pub fn execute_timed(self) -> Vec<Duration> {
+        self.steps
+            .into_iter()
+            .map(|step| {
+                let start = Instant::now();
+                step.execute();
+                start.elapsed()
+            })
+            .collect()
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, and reports how many steps ran and how long they took.
+    #[cfg(feature = "std")]
+    pub fn execute_reported(self) -> ExecutionReport
+    where
+        C: AsRef<[T]>,
+    {
+        let total_steps = self.len();
+        let start = Instant::now();
+        let step_durations = self.execute_timed();
+
+        ExecutionReport {
+            total_steps,
+            steps_run: step_durations.len(),
+            total_duration: start.elapsed(),
+            step_durations,
+        }
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline.
+    /// Before each step `stop` is checked against it, and execution ends without running the first step it returns `true` for.
+    /// Returns the number of steps that were executed.
+    pub fn execute_until<F>(self, mut stop: F) -> usize
+    where
+        F: FnMut(&T) -> bool,
+    {
+        let mut executed = 0;
+        for step in self.steps {
+            if stop(&step) {
+                break;
+            }
+            step.execute();
+            executed += 1;
+        }
+
+        executed
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, until `cancel` is set.
+    /// The flag is checked before each step, so it can be set from another thread (e.g. a Ctrl-C handler)
+    /// to stop the pipeline between steps. Steps that already ran are not undone.
+    pub fn execute_cancellable(self, cancel: &AtomicBool) {
+        self.execute_until(|_| cancel.load(Ordering::SeqCst));
+    }
+
+    /// Creates an iterator that executes one step per call to `next()`, in order, consuming the pipeline.
+    /// This lets the caller interleave the progress of the pipeline with other work.
+    pub fn into_step_iter(self) -> StepIter<T, C::IntoIter> {
+        StepIter {
+            steps: self.steps.into_iter(),
+            step_type: PhantomData,
+        }
+    }
+
+    /// Executes the `steps` in order, consuming the pipeline, and returns the number of steps that were executed.
+    /// This is always the number of steps in the pipeline, which can be handy when it was built on the fly.
+    pub fn execute_counting(self) -> usize
+    where
+        C: AsRef<[T]>,
+    {
+        let count = self.len();
+        self.execute();
+
+        count
+    }
+}
+
+/// An iterator that lazily executes the steps of a `PipelineSeq`, created by `PipelineSeq::into_step_iter`.
+/// `I` is the iterator over the steps of the pipeline's container, which is a `Vec` for a `PipelineVec`.
+#[derive(Debug, Clone)]
+pub struct StepIter<T, I = alloc::vec::IntoIter<T>> {
+    /// The steps that are yet to execute.
+    steps: I,
+
+    /// Phantom data to remember the step type with.
+    step_type: PhantomData<T>,
+}
+
+/// Each call to `next()` executes the next step, returning `None` once every step has executed.
+impl<T, I> Iterator for StepIter<T, I>
+where
+    I: Iterator<Item = T>,
+    T: Execute,
+{
+    type Item = ();
+
+    fn next(&mut self) -> Option<()> {
+        self.steps.next().map(|step| step.execute())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.steps.size_hint()
+    }
+}
+
+impl<T, I> ExactSizeIterator for StepIter<T, I>
+where
+    I: ExactSizeIterator<Item = T>,
+    T: Execute,
+{
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: ExecuteIndexed,
+{
+    /// Executes the `steps` in order, consuming the pipeline, passing each its zero-based index in the pipeline.
+    pub fn execute_indexed(self) {
+        for (index, step) in self.steps.into_iter().enumerate() {
+            step.execute_indexed(index)
+        }
+    }
+}
+
+/// `ExecuteIndexed`-ing a `PipelineSeq<T, C>` executes it as a single step of an outer pipeline, e.g. a
+/// `PipelineVec<PipelineVec<T>>`. `index` is the position of this pipeline in the outer one, and isn't passed on,
+/// since each of the `steps` is passed its zero-based index in this pipeline instead.
+impl<T, C> ExecuteIndexed for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: ExecuteIndexed,
+{
+    fn execute_indexed(self, _index: usize) {
+        PipelineSeq::execute_indexed(self)
+    }
+}
+
+/// `ExecuteInPlace`-ing a `PipelineSeq<T, C>` executes the `steps` in place, in order, keeping the pipeline and
+/// any changes the steps made to themselves.
+impl<T, C> ExecuteInPlace for PipelineSeq<T, C>
+where
+    C: AsMut<[T]>,
+    T: ExecuteInPlace,
+{
+    fn execute(&mut self) {
+        for step in self.iter_mut() {
+            ExecuteInPlace::execute(step)
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: Clone + Execute,
+{
+    /// Executes a clone of each of the `steps` in order, leaving the pipeline intact, for steps that can only be
+    /// executed by value. This pays for one clone per step, per execution.
+    pub fn execute_cloned(&self) {
+        for step in self.iter() {
+            step.clone().execute()
+        }
+    }
+}
+
+/// `ExecuteRef`-ing a `PipelineSeq<T, C>` executes each of the `steps` by reference in order, leaving the pipeline
+/// intact. Steps that only implement `Execute` can be executed without consuming the pipeline by `execute_cloned`.
+impl<T, C> ExecuteRef for PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: ExecuteRef,
+{
+    fn execute_ref(&self) {
+        for step in self.iter() {
+            step.execute_ref()
+        }
+    }
+}
+
+/// `TryExecute`-ing a `PipelineSeq<T, C>` executes the `steps` in order, stopping at the first failing step.
+/// The error carries the index of the failing step alongside its error.
+impl<T, C> TryExecute for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: TryExecute,
+{
+    type Error = (usize, T::Error);
+
+    fn try_execute(self) -> Result<(), Self::Error> {
+        for (index, step) in self.steps.into_iter().enumerate() {
+            step.try_execute().map_err(|e| (index, e))?
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: TryExecute,
+{
+    /// Executes every one of the `steps` in order regardless of failures, consuming the pipeline.
+    /// Returns the index and error of each failing step, so all of the problems can be reported at once.
+    pub fn execute_collect_errors(self) -> Vec<(usize, T::Error)> {
+        self.steps
+            .into_iter()
+            .enumerate()
+            .filter_map(|(index, step)| step.try_execute().err().map(|e| (index, e)))
+            .collect()
+    }
+}
+
+/// `ExecuteScan`-ing a `PipelineSeq<T, C>` executes the `steps` in order, threading the context and accumulator
+/// through each step and collecting each step's output.
+impl<T, C, Ctx, Acc> ExecuteScan<Ctx, Acc> for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: ExecuteScan<Ctx, Acc>,
+{
+    type Output = Vec<T::Output>;
+
+    fn execute_scan(self, ctx: &Ctx, acc: &mut Acc) -> Self::Output {
+        self.steps
+            .into_iter()
+            .map(|step| step.execute_scan(ctx, acc))
+            .collect()
+    }
+}
+
+/// `ExecuteCollect`-ing a `PipelineSeq<T, C>` executes the `steps` in order, collecting each step's output.
+impl<T, C> ExecuteCollect for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: ExecuteCollect,
+{
+    type Output = Vec<T::Output>;
+
+    fn execute_collect(self) -> Self::Output {
+        self.steps
+            .into_iter()
+            .map(|step| step.execute_collect())
+            .collect()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute + UnwindSafe,
+{
+    /// Executes the `steps` in order, consuming the pipeline, catching a panic in any step so that the rest still execute.
+    /// Returns the outcome of each step, where a panicking step has the panic payload as its error.
+    /// The panic hook still runs for each panic, so panic messages are printed as usual.
+    pub fn execute_catch(self) -> Vec<Result<(), Box<dyn Any + Send>>> {
+        self.steps
+            .into_iter()
+            .map(|step| panic::catch_unwind(move || step.execute()))
+            .collect()
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsMut<[T]> + IntoIterator<Item = T>,
+    T: Execute + Prioritized,
+{
+    /// Executes the `steps` from the highest to the lowest priority, consuming the pipeline.
+    /// Steps with the same priority keep their relative order.
+    pub fn execute_by_priority(mut self) {
+        // `sort_by_key` is stable, so ties keep their insertion order
+        self.steps
+            .as_mut()
+            .sort_by_key(|step| core::cmp::Reverse(step.priority()));
+        self.execute()
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]> + IntoIterator<Item = T>,
+    T: TwoPhase,
+{
+    /// Prepares all of the `steps` in order, then commits them in order, consuming the pipeline.
+    /// If any step fails to prepare, nothing is committed, and the index and failure of that step are returned.
+    pub fn execute_two_phase(self) -> Result<(), (usize, String)> {
+        for (index, step) in self.iter().enumerate() {
+            step.prepare().map_err(|e| (index, e))?;
+        }
+
+        for step in self.steps {
+            step.commit()
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute + Labeled,
+{
+    /// Executes only the `steps` whose label is one of `labels`, in order, consuming the pipeline.
+    /// The other steps are dropped without executing.
+    pub fn execute_only(self, labels: &[&str]) {
+        for step in self.steps {
+            if labels.contains(&step.label()) {
+                step.execute()
+            }
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: Execute + Phased,
+{
+    /// Executes only the `steps` in `phase`, in order, consuming the pipeline.
+    /// The other steps are dropped without executing.
+    pub fn execute_phase(self, phase: &str) {
+        for step in self.steps {
+            if step.phase() == Some(phase) {
+                step.execute()
+            }
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]> + IntoIterator<Item = T>,
+    T: Execute + HasDeps,
+{
+    /// Executes the `steps` in dependency order, consuming the pipeline, so that each step executes after every
+    /// step of the variants it depends on. Steps are otherwise kept in order, and dependencies on variants that
+    /// aren't in the pipeline are ignored. Nothing is executed if the dependencies are cyclic.
+    pub fn execute_topo(self) -> Result<(), CycleError> {
+        let steps = self.steps.as_ref();
+        let deps: Vec<Vec<usize>> = steps
+            .iter()
+            .map(|step| {
+                (0..steps.len())
+                    .filter(|&dep| step.after().contains(&steps[dep].variant_name()))
+                    .collect()
+            })
+            .collect();
+
+        // the first step whose dependencies have all been ordered goes next, which keeps the order stable
+        let mut ordered = alloc::vec![false; steps.len()];
+        let mut order = Vec::with_capacity(steps.len());
+        while order.len() < steps.len() {
+            let next = (0..steps.len())
+                .find(|&index| !ordered[index] && deps[index].iter().all(|&dep| ordered[dep]));
+
+            match next {
+                Some(index) => {
+                    ordered[index] = true;
+                    order.push(index);
+                }
+                None => {
+                    return Err(CycleError {
+                        steps: (0..steps.len()).filter(|&index| !ordered[index]).collect(),
+                    })
+                }
+            }
+        }
+
+        let mut steps: Vec<Option<T>> = self.steps.into_iter().map(Some).collect();
+        for index in order {
+            if let Some(step) = steps[index].take() {
+                step.execute()
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: Phased,
+{
+    /// The distinct phases of the `steps`, in the order they first appear.
+    pub fn phases(&self) -> Vec<&str> {
+        let mut phases = Vec::new();
+        for phase in self.iter().filter_map(Phased::phase) {
+            if !phases.contains(&phase) {
+                phases.push(phase);
+            }
+        }
+
+        phases
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: Labeled,
+{
+    /// Summarizes the pipeline for tooling, with the label of each step in execution order.
+    /// Steps aren't required to be `Prioritized`, so the priorities are all `None`, see `info_with_priorities`.
+    pub fn info(&self) -> PipelineInfo {
+        PipelineInfo {
+            len: self.len(),
+            labels: self
+                .iter()
+                .map(|step| Some(step.label().to_string()))
+                .collect(),
+            priorities: self.iter().map(|_| None).collect(),
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: Labeled + Prioritized,
+{
+    /// Summarizes the pipeline for tooling, with the label and priority of each step in execution order.
+    pub fn info_with_priorities(&self) -> PipelineInfo {
+        PipelineInfo {
+            priorities: self.iter().map(|step| Some(step.priority())).collect(),
+            ..self.info()
+        }
+    }
+}
+
+impl<T, C> PipelineSeq<T, C>
+where
+    C: AsRef<[T]>,
+    T: Describe,
+{
+    /// Describes each of the `steps` in execution order, without executing them.
+    pub fn describe(&self) -> Vec<String> {
+        self.iter().map(|step| step.describe()).collect()
+    }
+}
+
+/// `Execute`-ing to a `PipelineSeq<T, C>` asynchronously awaits each of the `steps` in order.
+#[cfg(feature = "async")]
+impl<T, C> ExecuteAsync for PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T> + Send + 'static,
+    C::IntoIter: Send,
+    T: ExecuteAsync + Send + 'static,
+{
+    fn execute_async(self) -> ExecuteFuture {
+        Box::pin(async move {
+            for step in self.steps {
+                step.execute_async().await
+            }
+        })
+    }
+}
+
+#[cfg(feature = "async")]
+impl<T, C> PipelineSeq<T, C>
+where
+    C: IntoIterator<Item = T>,
+    T: ExecuteAsync,
+{
+    /// Executes all of the steps concurrently, completing once every step has completed, consuming the pipeline.
+    /// The step futures are polled together, so the ordering of their side-effects is not guaranteed and
+    /// this is only suitable for steps that don't depend on each other.
+    pub async fn execute_concurrent(self) {
+        futures::future::join_all(self.steps.into_iter().map(|step| step.execute_async())).await;
+    }
+}
+
+/// Creates a `PipelineVec` from any `Vec<>`, for steps that aren't `Execute`-able (e.g. `TryExecute`).
+impl<T> From<Vec<T>> for PipelineVec<T> {
+    fn from(steps: Vec<T>) -> Self {
+        PipelineVec {
+            steps,
+            step_type: PhantomData,
+        }
+    }
+}
+
+/// Creates a `PipelineVecWith` from any `Vec<>`, for steps that aren't `ExecuteWith`-able (e.g. `TryExecuteWith`).
+impl<T, TArg> From<Vec<T>> for PipelineVecWith<T, TArg> {
+    fn from(steps: Vec<T>) -> Self {
+        PipelineVecWith {
+            steps,
+            arg_type: PhantomData,
+        }
+    }
+}
+
+/// Collects the items of an iterator into a `PipelineVec`, in iteration order.
+impl<T> FromIterator<T> for PipelineVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        PipelineVec {
+            steps: iter.into_iter().collect(),
+            step_type: PhantomData,
+        }
+    }
+}
+
+/// Appends the items of an iterator to the end of a `PipelineVec`, in iteration order.
+impl<T> Extend<T> for PipelineVec<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.steps.extend(iter)
+    }
+}
+
+/// A pipeline deque which represents a series of `Execute`-able operations, that can be cheaply added to or removed from at either end.
+#[derive(Debug, Clone)]
+pub struct PipelineDeque<T> {
+    /// The ordered step of operations.
+    steps: VecDeque<T>,
+}
+
+impl<T> PipelineDeque<T> {
+    /// Creates a pipeline with no steps, which does nothing when executed.
+    pub fn new() -> Self {
+        PipelineDeque {
+            steps: VecDeque::new(),
+        }
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Prepends a step, so that it executes before the existing steps.
+    pub fn push_front(&mut self, step: T) {
+        self.steps.push_front(step)
+    }
+
+    /// Appends a step, so that it executes after the existing steps.
+    pub fn push_back(&mut self, step: T) {
+        self.steps.push_back(step)
+    }
+
+    /// Removes and returns the next step to execute, or `None` if the pipeline is empty.
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.steps.pop_front()
+    }
+}
+
+impl<T> Default for PipelineDeque<T> {
+    fn default() -> Self {
+        PipelineDeque::new()
+    }
+}
+
+/// `Execute`-ing a `PipelineDeque<T>` drains the `steps` from front to back, executing each in turn.
+impl<T> Execute for PipelineDeque<T>
+where
+    T: Execute,
+{
+    fn execute(self) {
+        for step in self.steps {
+            step.execute()
+        }
+    }
+}
+
+/// A pipeline over borrowed steps, e.g. a `&Vec<T>` that should be kept after executing it.
+/// Since `Execute` consumes each step, executing a `PipelineSlice` requires `T: Clone` and pays for one clone per step.
+#[derive(Debug, Clone, Copy)]
+pub struct PipelineSlice<'a, T> {
+    /// The ordered step of operations.
+    steps: &'a [T],
+}
+
+impl<'a, T> PipelineSlice<'a, T> {
+    /// Creates a pipeline that executes clones of `steps` in order.
+    pub fn new(steps: &'a [T]) -> Self {
+        PipelineSlice { steps }
+    }
+}
+
+impl<'a, T> From<&'a [T]> for PipelineSlice<'a, T> {
+    fn from(steps: &'a [T]) -> Self {
+        PipelineSlice::new(steps)
+    }
+}
+
+/// `Execute`-ing a `PipelineSlice<T>` executes a clone of each of the borrowed `steps` in order.
+impl<'a, T> Execute for PipelineSlice<'a, T>
+where
+    T: Clone + Execute,
+{
+    fn execute(self) {
+        for step in self.steps {
+            step.clone().execute()
+        }
+    }
+}
+
+/// The outcome of executing a pipeline, returned by `PipelineVec::execute_reported`.
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionReport {
+    /// The number of steps in the pipeline.
+    pub total_steps: usize,
+    /// The number of steps that were executed.
+    pub steps_run: usize,
+    /// How long executing the whole pipeline took.
+    pub total_duration: Duration,
+    /// How long each executed step took, in step order.
+    pub step_durations: Vec<Duration>,
+}
+
+/// Structured information about the steps of a pipeline, returned by `PipelineVec::info`.
+/// The metadata of each step is in execution order, and is `None` where the steps don't provide it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineInfo {
+    /// The number of steps in the pipeline.
+    pub len: usize,
+    /// The label of each step.
+    pub labels: Vec<Option<String>>,
+    /// The priority of each step.
+    pub priorities: Vec<Option<i32>>,
+}
+
+/// A pipeline of a fixed number of steps, stored inline rather than on the heap, e.g. for embedded use
+/// where the steps are known up front.
+#[derive(Debug, Clone)]
+pub struct PipelineArray<T, const N: usize> {
+    /// The ordered step of operations.
+    steps: [T; N],
+}
+
+impl<T, const N: usize> PipelineArray<T, N> {
+    /// Creates a pipeline that executes `steps` in order.
+    pub fn new(steps: [T; N]) -> Self {
+        PipelineArray { steps }
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub const fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns whether the pipeline has no steps.
+    pub const fn is_empty(&self) -> bool {
+        N == 0
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for PipelineArray<T, N> {
+    fn from(steps: [T; N]) -> Self {
+        PipelineArray::new(steps)
+    }
+}
+
+/// `Execute`-ing a `PipelineArray<T, N>` executes the `steps` in order, without allocating.
+impl<T, const N: usize> Execute for PipelineArray<T, N>
+where
+    T: Execute,
+{
+    fn execute(self) {
+        for step in IntoIterator::into_iter(self.steps) {
+            step.execute()
+        }
+    }
+}
+
+/// A builder for assembling a `PipelineVec` one step at a time.
+pub struct PipelineBuilder<T> {
+    /// The ordered step of operations added so far.
+    steps: Vec<T>,
+}
+
+impl<T> PipelineBuilder<T> {
+    /// Creates a builder with no steps.
+    pub fn new() -> Self {
+        PipelineBuilder { steps: Vec::new() }
+    }
+
+    /// Creates a builder with no steps, with room for at least `capacity` steps before reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        PipelineBuilder {
+            steps: Vec::with_capacity(capacity),
+        }
+    }
+
+    /// Appends a step, returning the builder for chaining.
+    pub fn step(mut self, step: T) -> Self {
+        self.steps.push(step);
+        self
+    }
+
+    /// Creates a `PipelineVec` from the steps added so far, consuming the builder.
+    pub fn build(self) -> PipelineVec<T> {
+        PipelineVec {
+            steps: self.steps,
+            step_type: PhantomData,
+        }
+    }
+}
+
+impl<T> Default for PipelineBuilder<T> {
+    fn default() -> Self {
+        PipelineBuilder::new()
+    }
+}
+
+/// A pipeline vector which represents a series of `ExecuteWith`-able operations with an argument of type `TArg`.
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct PipelineVecWith<T, TArg> {
+    /// The ordered step of operations.
+    steps: Vec<T>,
+
+    /// Phantom data to remember the argument type with.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    arg_type: PhantomData<TArg>,
+}
+
+// implemented manually, since deriving would also require `TArg: Debug` or `TArg: Clone`
+impl<T: fmt::Debug, TArg> fmt::Debug for PipelineVecWith<T, TArg> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineVecWith")
+            .field("steps", &self.steps)
+            .finish()
+    }
+}
+
+impl<T: Clone, TArg> Clone for PipelineVecWith<T, TArg> {
+    fn clone(&self) -> Self {
+        PipelineVecWith {
+            steps: self.steps.clone(),
+            arg_type: PhantomData,
+        }
+    }
+}
+
+impl<T, TArg> Default for PipelineVecWith<T, TArg> {
+    fn default() -> Self {
+        PipelineVecWith::new()
+    }
+}
+
+impl<T, TArg> PipelineVecWith<T, TArg> {
+    /// Creates a pipeline with no steps, which does nothing when executed.
+    pub fn new() -> Self {
+        PipelineVecWith {
+            steps: Vec::new(),
+            arg_type: PhantomData,
+        }
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Appends a step to the end of the pipeline.
+    pub fn push(&mut self, step: T) {
+        self.steps.push(step)
+    }
+}
+
+/// `Execute`-ing to a `PipelineVecWith<T, TArg>` executes the `steps` in order, passing `arg` along.
+impl<T, TArg> ExecuteWith<TArg> for PipelineVecWith<T, TArg>
+where
+    T: ExecuteWith<TArg>,
+{
+    fn execute(self, arg: &TArg) {
+        for step in self.steps {
+            step.execute(arg)
+        }
+    }
+}
+
+impl<T, TArg> PipelineVecWith<T, TArg>
+where
+    T: ExecuteWith<TArg>,
+{
+    /// Executes the `steps` in reverse order, passing `arg` along, consuming the pipeline.
+    pub fn execute_rev(self, arg: &TArg) {
+        for step in self.steps.into_iter().rev() {
+            step.execute(arg)
+        }
+    }
+
+    /// Executes the `steps` in order, passing `arg` along, consuming the pipeline.
+    /// Before each step `pred` is checked against `arg`, and the step is skipped if it returns `false`.
+    pub fn execute_filtered<F>(self, arg: &TArg, pred: F)
+    where
+        F: Fn(&TArg) -> bool,
+    {
+        for step in self.steps {
+            if pred(arg) {
+                step.execute(arg)
+            }
+        }
+    }
+}
+
+impl<T, TArg> PipelineVecWith<T, TArg>
+where
+    T: ExecuteWith<TArg>,
+    TArg: Clone,
+{
+    /// Creates a plain `PipelineVec` where each step is bound to a clone of `arg`, consuming the pipeline.
+    /// Executing it executes the steps in order, as if by `execute(&arg)`.
+    pub fn bind(self, arg: TArg) -> PipelineVec<Bound<T, TArg>> {
+        self.steps
+            .into_iter()
+            .map(|step| Bound::new(step, arg.clone()))
+            .collect()
+    }
+}
+
+/// An `ExecuteWith`-able step bound to its argument, which makes it `Execute`-able.
+#[derive(Debug, Clone)]
+pub struct Bound<T, TArg> {
+    /// The step to execute.
+    step: T,
+
+    /// The argument to execute the step with.
+    arg: TArg,
+}
+
+impl<T, TArg> Bound<T, TArg> {
+    /// Binds `step` to `arg`.
+    pub fn new(step: T, arg: TArg) -> Self {
+        Bound { step, arg }
+    }
+}
+
+/// `Execute`-ing a `Bound<T, TArg>` executes the step with its bound argument.
+impl<T, TArg> Execute for Bound<T, TArg>
+where
+    T: ExecuteWith<TArg>,
+{
+    fn execute(self) {
+        self.step.execute(&self.arg)
+    }
+}
+
+impl<T, TArg> PipelineVecWith<T, TArg>
+where
+    T: TryExecuteWith<TArg>,
+{
+    /// Executes the `steps` in order, passing `arg` along, consuming the pipeline.
+    /// Execution stops at the first failing step, returning its index along with its error.
+    pub fn execute_with_try(self, arg: &TArg) -> Result<(), (usize, T::Error)> {
+        for (index, step) in self.steps.into_iter().enumerate() {
+            step.try_execute(arg).map_err(|e| (index, e))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// `Execute`-ing to a `PipelineVecWith<T, TArg>` executes the `steps` in order, passing a mutable `arg` along.
+impl<T, TArg> ExecuteWithMut<TArg> for PipelineVecWith<T, TArg>
+where
+    T: ExecuteWithMut<TArg>,
+{
+    fn execute(self, arg: &mut TArg) {
+        for step in self.steps {
+            step.execute(arg)
+        }
+    }
+}
+
+impl<T, TArg> PipelineVecWith<T, TArg>
+where
+    T: ExecuteWithMut<TArg>,
+{
+    /// Executes the `steps` in reverse order, passing a mutable `arg` along, consuming the pipeline.
+    pub fn execute_rev_mut(self, arg: &mut TArg) {
+        for step in self.steps.into_iter().rev() {
+            step.execute(arg)
+        }
+    }
+
+    /// Executes the `steps` in order, passing along a mutable `arg` that starts as `init`, consuming the pipeline.
+    /// Returns the final `arg` once every step has mutated it.
+    pub fn execute_fold(self, init: TArg) -> TArg {
+        let mut arg = init;
+        self.execute(&mut arg);
+        arg
+    }
+
+    /// Executes the `steps` in order, passing a mutable `arg` along, consuming the pipeline.
+    /// Before each step `pred` is checked against the current `arg`, and the step is skipped if it returns `false`.
+    /// Since steps may mutate `arg`, a step can change whether the steps after it run.
+    pub fn execute_filtered_mut<F>(self, arg: &mut TArg, pred: F)
+    where
+        F: Fn(&TArg) -> bool,
+    {
+        for step in self.steps {
+            if pred(arg) {
+                step.execute(arg)
+            }
+        }
+    }
+}
+
+/// A pipeline vector which represents a series of `ExecuteWith2`-able operations with arguments of type `TArg1` and `TArg2`.
+pub struct PipelineVecWith2<T, TArg1, TArg2> {
+    /// The ordered step of operations.
+    steps: Vec<T>,
+
+    /// Phantom data to remember the argument types with.
+    arg_types: PhantomData<(TArg1, TArg2)>,
+}
+
+impl<T: fmt::Debug, TArg1, TArg2> fmt::Debug for PipelineVecWith2<T, TArg1, TArg2> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PipelineVecWith2")
+            .field("steps", &self.steps)
+            .finish()
+    }
+}
+
+impl<T: Clone, TArg1, TArg2> Clone for PipelineVecWith2<T, TArg1, TArg2> {
+    fn clone(&self) -> Self {
+        PipelineVecWith2 {
+            steps: self.steps.clone(),
+            arg_types: PhantomData,
+        }
+    }
+}
+
+impl<T, TArg1, TArg2> Default for PipelineVecWith2<T, TArg1, TArg2> {
+    fn default() -> Self {
+        PipelineVecWith2::new()
+    }
+}
+
+impl<T, TArg1, TArg2> PipelineVecWith2<T, TArg1, TArg2> {
+    /// Creates a pipeline with no steps, which does nothing when executed.
+    pub fn new() -> Self {
+        PipelineVecWith2 {
+            steps: Vec::new(),
+            arg_types: PhantomData,
+        }
+    }
+
+    /// Returns the number of steps in the pipeline.
+    pub fn len(&self) -> usize {
+        self.steps.len()
+    }
+
+    /// Returns `true` if the pipeline has no steps.
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Appends a step to the end of the pipeline.
+    pub fn push(&mut self, step: T) {
+        self.steps.push(step)
+    }
+}
+
+/// `Execute`-ing to a `PipelineVecWith2<T, TArg1, TArg2>` executes the `steps` in order, passing `a1` and a mutable `a2` along.
+impl<T, TArg1, TArg2> ExecuteWith2<TArg1, TArg2> for PipelineVecWith2<T, TArg1, TArg2>
+where
+    T: ExecuteWith2<TArg1, TArg2>,
+{
+    fn execute(self, a1: &TArg1, a2: &mut TArg2) {
+        for step in self.steps {
+            step.execute(a1, a2)
+        }
+    }
+}
+
+/// Provides a way to convert into a `PipelineVec` for ordered execution.
+pub trait IntoPipelineVec<T>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineVec` that can be executed, consuming the source.
+    fn into_pipeline(self) -> PipelineVec<T>;
+}
+
+/// Provides a way to convert into a `PipelineVecWith` for ordered execution with an argument of type `TArg`.
+pub trait IntoPipelineVecWith<T, TArg>
+where
+    T: ExecuteWith<TArg>,
+{
+    /// Creates a `PipelineVecWith` that can be executed with an argument, consuming the source.
+    fn into_pipeline(self) -> PipelineVecWith<T, TArg>;
+}
+
+/// Provides a way to convert into a `PipelineVecWith` for ordered execution with a mutable argument of type `TArg`.
+pub trait IntoPipelineVecWithMut<T, TArg>
+where
+    T: ExecuteWithMut<TArg>,
+{
+    /// Creates a `PipelineVecWith` that can be executed with a mutable argument, consuming the source.
+    fn into_pipeline(self) -> PipelineVecWith<T, TArg>;
+}
+
+/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVec` for execution.
+impl<T> IntoPipelineVec<T> for Vec<T>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineVec` that can be executed, consuming the source `Vec`.
+    fn into_pipeline(self) -> PipelineVec<T> {
+        PipelineVec {
+            steps: self,
+            step_type: PhantomData,
+        }
+    }
+}
+
+/// Provides a way to convert an array of `Execute`-able elements into a `PipelineVec` for execution.
+impl<T, const N: usize> IntoPipelineVec<T> for [T; N]
+where
+    T: Execute,
+{
+    /// Creates a `PipelineVec` that can be executed, moving the elements out of the source array.
+    fn into_pipeline(self) -> PipelineVec<T> {
+        Vec::from(self).into_pipeline()
+    }
+}
+
+/// Provides a way to convert a slice of `Execute`-able elements into a `PipelineVec` for execution.
+impl<T> IntoPipelineVec<T> for &[T]
+where
+    T: Execute + Clone,
+{
+    /// Creates a `PipelineVec` that can be executed, cloning the elements of the source slice.
+    fn into_pipeline(self) -> PipelineVec<T> {
+        self.to_vec().into_pipeline()
+    }
+}
+
+/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVecWith` for ordered execution with an argument of type `TArg`.
+impl<T, TArg> IntoPipelineVecWith<T, TArg> for Vec<T>
+where
+    T: ExecuteWith<TArg>,
+{
+    /// Creates a `PipelineVecWith` that can be executed with an argument, consuming the source `Vec`.
+    fn into_pipeline(self) -> PipelineVecWith<T, TArg> {
+        PipelineVecWith {
+            steps: self,
+            arg_type: PhantomData,
+        }
+    }
+}
+
+/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVecWithMut` for ordered execution with an argument of type `TArg`.
+impl<T, TArg> IntoPipelineVecWithMut<T, TArg> for Vec<T>
+where
+    T: ExecuteWithMut<TArg>,
+{
+    /// Creates a `PipelineVecWith` that can be executed with a mutable argument, consuming the source `Vec`.
+    fn into_pipeline(self) -> PipelineVecWith<T, TArg> {
+        PipelineVecWith {
+            steps: self,
+            arg_type: PhantomData,
+        }
+    }
+}
+
+/// Implements `IntoPipelineVec`, `IntoPipelineVecWith` and `IntoPipelineVecWithMut` for a container of steps,
+/// collecting the steps in iteration order, so that custom collections get `into_pipeline()` like a `Vec` does.
+/// The container must be `IntoIterator`, and its items are the steps. Given the name of a container generic over
+/// its steps, e.g. `impl_into_pipeline!(MyVec)`, implements the traits for `MyVec<T>`. Other containers are given
+/// as a type, after the generic parameters of the impl in brackets (without bounds or a trailing comma), e.g.
+/// `impl_into_pipeline!(['a, T] my_mod::Arena<'a, T>)` or `impl_into_pipeline!(my_mod::OpList)`.
+#[macro_export]
+macro_rules! impl_into_pipeline {
+    ($container:ident) => {
+        $crate::impl_into_pipeline!(@impls [T] [T, __TArg] $container<T>);
+    };
+    ([] $container:ty) => {
+        $crate::impl_into_pipeline!(@impls [] [__TArg] $container);
+    };
+    ([$($generics:tt)*] $container:ty) => {
+        $crate::impl_into_pipeline!(@impls [$($generics)*] [$($generics)*, __TArg] $container);
+    };
+    ($container:ty) => {
+        $crate::impl_into_pipeline!(@impls [] [__TArg] $container);
+    };
+    (@impls [$($generics:tt)*] [$($generics_with_arg:tt)*] $container:ty) => {
+        impl<$($generics)*> $crate::IntoPipelineVec<<$container as ::core::iter::IntoIterator>::Item>
+            for $container
+        where
+            $container: ::core::iter::IntoIterator,
+            <$container as ::core::iter::IntoIterator>::Item: $crate::Execute,
+        {
+            fn into_pipeline(
+                self,
+            ) -> $crate::PipelineVec<<$container as ::core::iter::IntoIterator>::Item> {
+                ::core::iter::Iterator::collect(::core::iter::IntoIterator::into_iter(self))
+            }
+        }
+
+        impl<$($generics_with_arg)*>
+            $crate::IntoPipelineVecWith<<$container as ::core::iter::IntoIterator>::Item, __TArg>
+            for $container
+        where
+            $container: ::core::iter::IntoIterator,
+            <$container as ::core::iter::IntoIterator>::Item: $crate::ExecuteWith<__TArg>,
+        {
+            fn into_pipeline(
+                self,
+            ) -> $crate::PipelineVecWith<<$container as ::core::iter::IntoIterator>::Item, __TArg>
+            {
+                $crate::PipelineVecWith::from(::core::iter::Iterator::collect::<
+                    $crate::__private::Vec<_>,
+                >(::core::iter::IntoIterator::into_iter(self)))
+            }
+        }
+
+        impl<$($generics_with_arg)*>
+            $crate::IntoPipelineVecWithMut<<$container as ::core::iter::IntoIterator>::Item, __TArg>
+            for $container
+        where
+            $container: ::core::iter::IntoIterator,
+            <$container as ::core::iter::IntoIterator>::Item: $crate::ExecuteWithMut<__TArg>,
+        {
+            fn into_pipeline(
+                self,
+            ) -> $crate::PipelineVecWith<<$container as ::core::iter::IntoIterator>::Item, __TArg>
+            {
+                $crate::PipelineVecWith::from(::core::iter::Iterator::collect::<
+                    $crate::__private::Vec<_>,
+                >(::core::iter::IntoIterator::into_iter(self)))
+            }
+        }
+    };
+}
+
+/// Items used by the expansion of this crate's macros, which aren't part of its API.
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::string::String;
+    pub use alloc::vec::Vec;
+}
+
+/// Provides a way to convert into a `PipelineDeque` for ordered execution.
+pub trait IntoPipelineDeque<T>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineDeque` that can be executed, consuming the source.
+    fn into_pipeline_deque(self) -> PipelineDeque<T>;
+}
+
+/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineDeque` for execution.
+impl<T> IntoPipelineDeque<T> for Vec<T>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineDeque` that can be executed, consuming the source `Vec`.
+    fn into_pipeline_deque(self) -> PipelineDeque<T> {
+        PipelineDeque { steps: self.into() }
+    }
+}
+
+/// Provides a way to convert a `VecDeque<>` of `Execute`-able elements into a `PipelineDeque` for execution.
+impl<T> IntoPipelineDeque<T> for VecDeque<T>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineDeque` that can be executed, consuming the source `VecDeque`.
+    fn into_pipeline_deque(self) -> PipelineDeque<T> {
+        PipelineDeque { steps: self }
+    }
+}
+
+/// Provides a way to convert into a `PipelineArray` for ordered execution without allocating.
+pub trait IntoPipelineArray<T, const N: usize>
+where
+    T: Execute,
+{
+    /// Creates a `PipelineArray` that can be executed, consuming the source.
+    fn into_pipeline_array(self) -> PipelineArray<T, N>;
+}
+
+/// Provides a way to convert an array of `Execute`-able elements into a `PipelineArray` for execution.
+impl<T, const N: usize> IntoPipelineArray<T, N> for [T; N]
+where
+    T: Execute,
+{
+    /// Creates a `PipelineArray` that can be executed, consuming the source array.
+    fn into_pipeline_array(self) -> PipelineArray<T, N> {
+        PipelineArray::new(self)
+    }
+}
+
+/// Provides a way to convert into a `PipelineVecWith2` for ordered execution with arguments of type `TArg1` and `TArg2`.
+pub trait IntoPipelineVecWith2<T, TArg1, TArg2>
+where
+    T: ExecuteWith2<TArg1, TArg2>,
+{
+    /// Creates a `PipelineVecWith2` that can be executed with two arguments, consuming the source.
+    fn into_pipeline(self) -> PipelineVecWith2<T, TArg1, TArg2>;
+}
+
+/// Provides a way to convert a `Vec<>` of `Execute`-able elements into a `PipelineVecWith2` for ordered execution with arguments of type `TArg1` and `TArg2`.
+impl<T, TArg1, TArg2> IntoPipelineVecWith2<T, TArg1, TArg2> for Vec<T>
+where
+    T: ExecuteWith2<TArg1, TArg2>,
+{
+    /// Creates a `PipelineVecWith2` that can be executed with two arguments, consuming the source `Vec`.
+    fn into_pipeline(self) -> PipelineVecWith2<T, TArg1, TArg2> {
+        PipelineVecWith2 {
+            steps: self,
+            arg_types: PhantomData,
+        }
+    }
+}
+
+/// The error produced when a step fails validation while building a pipeline.
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildError {
+    /// The index of the first step that failed validation.
+    pub index: usize,
+    /// The validation failure of that step.
+    pub message: String,
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "step {} is invalid: {}", self.index, self.message)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BuildError {}
+
+/// The error produced when the steps of a pipeline can't be ordered by their dependencies.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// The indices of the steps that are in a dependency cycle, or depend on a step that is.
+    pub steps: Vec<usize>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "steps {:?} have cyclic dependencies", self.steps)
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for CycleError {}
+
+/// Provides a way to convert into a `PipelineVec` for ordered execution, validating each step first.
+pub trait TryIntoPipelineVec<T>
+where
+    T: Execute + Validate,
+{
+    /// Creates a `PipelineVec` that can be executed if every step is valid, consuming the source.
+    fn try_into_pipeline(self) -> Result<PipelineVec<T>, BuildError>;
+}
+
+/// Provides a way to convert a `Vec<>` of `Validate`-able elements into a `PipelineVec`, failing at the first invalid step.
+impl<T> TryIntoPipelineVec<T> for Vec<T>
+where
+    T: Execute + Validate,
+{
+    /// Creates a `PipelineVec` that can be executed if every step is valid, consuming the source `Vec`.
+    fn try_into_pipeline(self) -> Result<PipelineVec<T>, BuildError> {
+        for (index, step) in self.iter().enumerate() {
+            step.validate()
+                .map_err(|message| BuildError { index, message })?;
+        }
+
+        Ok(PipelineVec {
+            steps: self,
+            step_type: PhantomData,
+        })
+    }
+}
+
+/// Executes each item of `steps` in order, without collecting them into a `PipelineVec` first.
+pub fn run<I>(steps: I)
+where
+    I: IntoIterator,
+    I::Item: Execute,
+{
+    for step in steps {
+        step.execute()
+    }
+}
+
+/// Executes each step received from `rx` as it arrives, blocking while waiting for the next one, until
+/// every sender has been dropped. This turns the calling thread into a worker for the producers of the steps.
+#[cfg(feature = "std")]
+pub fn execute_from_channel<T>(rx: std::sync::mpsc::Receiver<T>)
+where
+    T: Execute,
+{
+    run(rx)
+}
+
+/// Executes each item of `steps` in order, passing `arg` along, without collecting them into a `PipelineVecWith` first.
+pub fn run_with<I, TArg>(steps: I, arg: &TArg)
+where
+    I: IntoIterator,
+    I::Item: ExecuteWith<TArg>,
+{
+    for step in steps {
+        step.execute(arg)
+    }
+}
+
+/// Executes each item of `steps` in order, passing a mutable `arg` along, without collecting them into a `PipelineVecWith` first.
+pub fn run_with_mut<I, TArg>(steps: I, arg: &mut TArg)
+where
+    I: IntoIterator,
+    I::Item: ExecuteWithMut<TArg>,
+{
+    for step in steps {
+        step.execute(arg)
+    }
+}
+
+/// The traits and derive macros of typical usage, so that `use enum_pipeline::prelude::*;` is enough to
+/// derive, build and execute a pipeline.
+pub mod prelude {
+    #[cfg(feature = "async")]
+    pub use crate::ExecuteAsync;
+    pub use crate::{
+        Describe, Execute, ExecuteCollect, ExecuteInPlace, ExecuteIndexed, ExecuteRef, ExecuteWith,
+        ExecuteWith2, ExecuteWithMut, HasDeps, IntoPipelineArray, IntoPipelineDeque,
+        IntoPipelineVec, IntoPipelineVecWith, IntoPipelineVecWith2, IntoPipelineVecWithMut,
+        Labeled, Phased, Pipeline, Prioritized, TryExecute, TryExecuteWith, TryIntoPipelineVec,
+        Validate, VariantIndex,
+    };
+    pub use enum_pipeline_derive::{
+        Constructors, Describe, Execute, HasDeps, Labeled, Phased, Prioritized, VariantIndex,
+    };
+}
+
+#[cfg(test)]
+mod readme_test {
+    use crate::{Execute, IntoPipelineVec};
+
+    enum Operations {
+        Allocate(f32, f32),
+        Init,
+        Run(f32),
+    }
+
+    impl Execute for Operations {
+        fn execute(self) {
+            match self {
+                Operations::Allocate(_x, _y) => println!("allocate something"),
+                Operations::Init => println!("init"),
+                Operations::Run(_delta) => println!("do work"),
+            }
+        }
+    }
+
+    #[test]
+    fn do_work() {
+        let my_op_pipeline = vec![
+            Operations::Init,
+            Operations::Allocate(1.0, 1.0),
+            Operations::Init,
+            Operations::Run(1.0),
+        ]
+        .into_pipeline();
+
+        my_op_pipeline.execute();
+        // prints:
+        // init
+        // allocate something
+        // init
+        // do work
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(feature = "std")]
+    use crate::execute_from_channel;
+    use crate::{
+        run, run_with, run_with_mut, Bound, BoxExecute, BuildError, CycleError, Describe, Execute,
+        ExecuteCollect, ExecuteInPlace, ExecuteIndexed, ExecuteRef, ExecuteScan, ExecuteWith,
+        ExecuteWith2, ExecuteWithMut, ExecutionStrategy, HasDeps, InlineStrategy,
+        IntoPipelineArray, IntoPipelineDeque, IntoPipelineVec, IntoPipelineVecWith,
+        IntoPipelineVecWith2, IntoPipelineVecWithMut, Labeled, Phased, Pipeline, PipelineArray,
+        PipelineBuilder, PipelineInfo, PipelineSlice, PipelineVec, PipelineVecWith, Prioritized,
+        TryExecute, TryExecuteWith, TryIntoPipelineVec, TwoPhase, Validate, VariantIndex,
+    };
+    use enum_pipeline_derive::{
+        Constructors, Describe, Execute, HasDeps, Labeled, Phased, Prioritized, VariantIndex,
+    };
+    use std::cell::{Cell, RefCell};
+    use std::rc::Rc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Mutex;
+
+    thread_local! {
+        static RECORDED_STEPS: RefCell<Vec<u32>> = const { RefCell::new(Vec::new()) };
+    }
+
+    // a step that records its value when executed, for tests that exercise the pipeline containers
+    #[derive(Debug, Clone, PartialEq)]
+    struct RecordingStep(u32);
+
+    impl Execute for RecordingStep {
+        fn execute(self) {
+            RECORDED_STEPS.with(|recorded| recorded.borrow_mut().push(self.0));
+        }
+    }
+
+    impl ExecuteRef for RecordingStep {
+        fn execute_ref(&self) {
+            self.clone().execute()
+        }
+    }
+
+    // the values recorded by `RecordingStep`s executed on the current thread
+    fn recorded_steps() -> Vec<u32> {
+        RECORDED_STEPS.with(|recorded| recorded.borrow().clone())
+    }
+
+    // a handler recording `v`, shared by the derived pipelines as #[handler(crate::tests::record)]
+    fn record(v: u32) {
+        RecordingStep(v).execute()
+    }
+
+    #[derive(Execute)]
+    enum VoidDispatchPipeline {
+        #[handler(VoidDispatchPipeline::handle_one)]
+        One,
+        #[handler(handle_two)]
+        Two,
+    }
+
+    static mut VOID_ONE_COUNT: i32 = 0;
+    static mut VOID_TWO_COUNT: i32 = 0;
+
+    impl VoidDispatchPipeline {
+        fn handle_one() {
+            unsafe {
+                VOID_ONE_COUNT += 1;
+            }
+        }
+
+        fn handle_two() {
+            unsafe {
+                VOID_TWO_COUNT += 1;
+            }
+        }
+    }
+
+    #[test]
+    fn void_dispatch_works() {
+        let pipeline = vec![VoidDispatchPipeline::One, VoidDispatchPipeline::Two].into_pipeline();
+
+        pipeline.execute();
+
+        let (one_count, two_count) = unsafe { (VOID_ONE_COUNT, VOID_TWO_COUNT) };
+
+        assert_eq!(1, one_count);
+        assert_eq!(1, two_count);
+    }
+
+    mod path_handlers {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        pub static RECORDED: AtomicUsize = AtomicUsize::new(0);
+
+        pub fn record(v: usize) {
+            RECORDED.fetch_add(v, Ordering::SeqCst);
+        }
+    }
+
+    #[derive(Execute)]
+    enum PathDispatchPipeline {
+        #[handler(crate::tests::path_handlers::record)]
+        Record(usize),
+        #[handler(::std::mem::drop)]
+        Release(Rc<()>),
+    }
+
+    #[test]
+    fn path_dispatch_works() {
+        let shared = Rc::new(());
+        let pipeline = vec![
+            PathDispatchPipeline::Record(2),
+            PathDispatchPipeline::Release(shared.clone()),
+            PathDispatchPipeline::Record(3),
+        ]
+        .into_pipeline();
+
+        assert_eq!(2, Rc::strong_count(&shared));
+
+        pipeline.execute();
+
+        assert_eq!(5, path_handlers::RECORDED.load(Ordering::SeqCst));
+        assert_eq!(1, Rc::strong_count(&shared));
+    }
+
+    enum RefDataPipeline {
+        One(f32),
+        Two,
+    }
+
+    static mut REF_ONE_VALUE: f32 = 0.0;
+    static mut REF_TWO_COUNT: i32 = 0;
+
+    struct RefDataPipelineData {
+        mult: f32,
+    }
+
+    impl RefDataPipeline {
+        fn handle_one(v: f32, arg: &RefDataPipelineData) {
+            unsafe {
+                REF_ONE_VALUE += v * arg.mult;
+            }
+        }
+
+        fn handle_two(_arg: &RefDataPipelineData) {
+            unsafe {
+                REF_TWO_COUNT += 1;
+            }
+        }
+    }
+
+    impl ExecuteWith<RefDataPipelineData> for RefDataPipeline {
+        fn execute(self, arg: &RefDataPipelineData) {
+            match self {
+                RefDataPipeline::One(f) => RefDataPipeline::handle_one(f, arg),
+                RefDataPipeline::Two => RefDataPipeline::handle_two(arg),
+            }
+        }
+    }
+
+    #[test]
+    fn ref_data_pipeline_works() {
+        let pipeline = vec![RefDataPipeline::One(24.0), RefDataPipeline::Two].into_pipeline();
+
+        let data = RefDataPipelineData { mult: 2.0 };
+
+        pipeline.execute(&data);
+
+        let (one_value, two_count) = unsafe { (REF_ONE_VALUE, REF_TWO_COUNT) };
+
+        assert_eq!(48.0, one_value);
+        assert_eq!(1, two_count);
+    }
+
+    #[derive(Execute)]
+    #[execute_with(DerivedRefDataPipelineData)]
+    enum DerivedRefDataPipeline {
+        #[handler(handle_one)]
+        One(f32),
+        #[handler(handle_two)]
+        Two,
+    }
+
+    struct DerivedRefDataPipelineData {
+        mult: f32,
+        one_value: Cell<f32>,
+        two_count: Cell<i32>,
+    }
+
+    impl DerivedRefDataPipeline {
+        fn handle_one(v: f32, arg: &DerivedRefDataPipelineData) {
+            arg.one_value.set(arg.one_value.get() + v * arg.mult);
+        }
+
+        fn handle_two(arg: &DerivedRefDataPipelineData) {
+            arg.two_count.set(arg.two_count.get() + 1);
+        }
+    }
+
+    #[test]
+    fn derived_ref_data_pipeline_works() {
+        let pipeline = vec![
+            DerivedRefDataPipeline::One(24.0),
+            DerivedRefDataPipeline::Two,
+        ]
+        .into_pipeline();
+
+        let data = DerivedRefDataPipelineData {
+            mult: 2.0,
+            one_value: Cell::new(0.0),
+            two_count: Cell::new(0),
+        };
+
+        pipeline.execute(&data);
+
+        assert_eq!(48.0, data.one_value.get());
+        assert_eq!(1, data.two_count.get());
+    }
+
+    enum MutDataPipeline {
+        One(f32),
+        Two,
+    }
+
+    #[derive(Default)]
+    struct MutDataPipelineData {
+        one_value: f32,
+        two_count: i32,
+    }
+
+    impl ExecuteWithMut<MutDataPipelineData> for MutDataPipeline {
+        fn execute(self, arg: &mut MutDataPipelineData) {
+            match self {
                 MutDataPipeline::One(f) => arg.one_value += f,
                 MutDataPipeline::Two => arg.two_count += 1,
             }
@@ -293,13 +2370,2874 @@ mod tests {
     }
 
     #[test]
-    fn mut_data_pipeline_works() {
-        let pipeline = vec![MutDataPipeline::One(12.0), MutDataPipeline::Two].into_pipeline();
+    fn mut_data_pipeline_works() {
+        let pipeline = vec![MutDataPipeline::One(12.0), MutDataPipeline::Two].into_pipeline();
+
+        let mut data = MutDataPipelineData::default();
+        pipeline.execute(&mut data);
+
+        assert_eq!(12.0, data.one_value);
+        assert_eq!(1, data.two_count);
+    }
+
+    #[derive(Execute, Debug, Clone)]
+    #[execute_with_mut(MutDataPipelineData)]
+    enum DerivedMutDataPipeline {
+        #[handler(Self::handle_one)]
+        One(f32),
+        #[handler(Self::handle_two)]
+        Two,
+    }
+
+    impl DerivedMutDataPipeline {
+        fn handle_one(v: f32, arg: &mut MutDataPipelineData) {
+            arg.one_value += v;
+        }
+
+        fn handle_two(arg: &mut MutDataPipelineData) {
+            arg.two_count += 1;
+        }
+    }
+
+    #[test]
+    fn derived_mut_data_pipeline_works() {
+        let pipeline = vec![
+            DerivedMutDataPipeline::One(12.0),
+            DerivedMutDataPipeline::Two,
+            DerivedMutDataPipeline::One(3.0),
+        ]
+        .into_pipeline();
+
+        let mut data = MutDataPipelineData::default();
+        pipeline.execute(&mut data);
+
+        assert_eq!(15.0, data.one_value);
+        assert_eq!(1, data.two_count);
+    }
+
+    #[derive(Execute)]
+    #[execute_with_mut(Vec<(u32, u32)>)]
+    enum NamedFieldPipeline {
+        #[handler(handle_resize)]
+        Resize { width: u32, height: u32 },
+    }
+
+    impl NamedFieldPipeline {
+        fn handle_resize(width: u32, height: u32, arg: &mut Vec<(u32, u32)>) {
+            arg.push((width, height));
+        }
+    }
+
+    #[test]
+    fn named_field_pipeline_works() {
+        let pipeline = vec![
+            NamedFieldPipeline::Resize {
+                width: 1,
+                height: 2,
+            },
+            NamedFieldPipeline::Resize {
+                height: 4,
+                width: 3,
+            },
+        ]
+        .into_pipeline();
+
+        let mut sizes = vec![];
+        pipeline.execute(&mut sizes);
+
+        assert_eq!(vec![(1, 2), (3, 4)], sizes);
+    }
+
+    #[derive(Execute)]
+    enum MultiHandlerPipeline {
+        #[handler(record_first)]
+        #[handler(record_second)]
+        One(String),
+        #[handler(record_second)]
+        Two(String),
+    }
+
+    static MULTI_HANDLER_ORDER: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl MultiHandlerPipeline {
+        fn record_first(v: String) {
+            MULTI_HANDLER_ORDER
+                .lock()
+                .unwrap()
+                .push(format!("first {}", v));
+        }
+
+        fn record_second(v: String) {
+            MULTI_HANDLER_ORDER
+                .lock()
+                .unwrap()
+                .push(format!("second {}", v));
+        }
+    }
+
+    #[test]
+    fn multi_handler_pipeline_works() {
+        let pipeline = vec![
+            MultiHandlerPipeline::One("one".to_string()),
+            MultiHandlerPipeline::Two("two".to_string()),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(
+            vec!["first one", "second one", "second two"],
+            *MULTI_HANDLER_ORDER.lock().unwrap()
+        );
+    }
+
+    #[derive(Execute)]
+    #[default_handler(Self::record_default)]
+    enum DefaultHandlerPipeline {
+        #[handler(record_explicit)]
+        One(u32),
+        Two(u32),
+        Three(u32),
+    }
+
+    static DEFAULT_HANDLER_CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl DefaultHandlerPipeline {
+        fn record_explicit(v: u32) {
+            DEFAULT_HANDLER_CALLS
+                .lock()
+                .unwrap()
+                .push(format!("explicit {}", v));
+        }
+
+        fn record_default(v: u32) {
+            DEFAULT_HANDLER_CALLS
+                .lock()
+                .unwrap()
+                .push(format!("default {}", v));
+        }
+    }
+
+    #[test]
+    fn default_handler_pipeline_works() {
+        let pipeline = vec![
+            DefaultHandlerPipeline::Two(2),
+            DefaultHandlerPipeline::One(1),
+            DefaultHandlerPipeline::Three(3),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(
+            vec!["default 2", "explicit 1", "default 3"],
+            *DEFAULT_HANDLER_CALLS.lock().unwrap()
+        );
+    }
+
+    #[derive(Execute)]
+    #[try_execute(String)]
+    enum FalliblePipeline {
+        #[handler(handle_record)]
+        Record(u32),
+        #[handler(handle_fail)]
+        Fail(String),
+    }
+
+    static FALLIBLE_RECORDED: Mutex<Vec<u32>> = Mutex::new(Vec::new());
+
+    impl FalliblePipeline {
+        fn handle_record(v: u32) -> Result<(), String> {
+            FALLIBLE_RECORDED.lock().unwrap().push(v);
+            Ok(())
+        }
+
+        fn handle_fail(reason: String) -> Result<(), String> {
+            Err(reason)
+        }
+    }
+
+    #[test]
+    fn fallible_pipeline_stops_at_first_error() {
+        let pipeline: PipelineVec<_> = vec![
+            FalliblePipeline::Record(1),
+            FalliblePipeline::Fail("broken".to_string()),
+            FalliblePipeline::Record(3),
+        ]
+        .into();
+
+        let res = pipeline.try_execute();
+
+        assert_eq!(Err((1, "broken".to_string())), res);
+        assert_eq!(vec![1], *FALLIBLE_RECORDED.lock().unwrap());
+    }
+
+    #[test]
+    fn pipeline_iteration_works() {
+        let mut pipeline =
+            vec![RecordingStep(1), RecordingStep(2), RecordingStep(3)].into_pipeline();
+
+        assert_eq!(3, pipeline.iter().count());
+        assert_eq!(
+            vec![&RecordingStep(1), &RecordingStep(2), &RecordingStep(3)],
+            (&pipeline).into_iter().collect::<Vec<_>>()
+        );
+
+        for step in pipeline.iter_mut() {
+            step.0 *= 10;
+        }
+        for step in &mut pipeline {
+            step.0 += 1;
+        }
+
+        assert_eq!(
+            vec![&RecordingStep(11), &RecordingStep(21), &RecordingStep(31)],
+            pipeline.iter().collect::<Vec<_>>()
+        );
+
+        pipeline.execute();
+
+        assert_eq!(vec![11, 21, 31], recorded_steps());
+
+        let owned = vec![RecordingStep(4), RecordingStep(5)].into_pipeline();
+
+        assert_eq!(
+            vec![RecordingStep(4), RecordingStep(5)],
+            owned.into_iter().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn pipeline_push_works() {
+        let mut pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        assert_eq!(2, pipeline.len());
+        assert!(!pipeline.is_empty());
+
+        pipeline.push(RecordingStep(3));
+
+        assert_eq!(3, pipeline.len());
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_with_push_works() {
+        let mut pipeline = vec![MutDataPipeline::One(1.0)].into_pipeline();
+
+        pipeline.push(MutDataPipeline::Two);
+        pipeline.push(MutDataPipeline::One(2.0));
+
+        assert_eq!(3, pipeline.len());
+        assert!(!pipeline.is_empty());
+
+        let mut data = MutDataPipelineData::default();
+        pipeline.execute(&mut data);
+
+        assert_eq!(3.0, data.one_value);
+        assert_eq!(1, data.two_count);
+    }
+
+    #[test]
+    fn pipeline_execute_ref_works() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.execute_ref();
+        pipeline.execute_ref();
+
+        assert_eq!(vec![1, 2, 1, 2], recorded_steps());
+        assert_eq!(2, pipeline.len());
+    }
+
+    #[test]
+    fn pipeline_execute_cloned_works() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.execute_cloned();
+        pipeline.execute_cloned();
+
+        assert_eq!(vec![1, 2, 1, 2], recorded_steps());
+        assert_eq!(2, pipeline.len());
+    }
+
+    #[derive(Execute)]
+    #[execute_collect(i32)]
+    enum CollectingPipeline {
+        #[handler(handle_double)]
+        Double(i32),
+        #[handler(handle_negate)]
+        Negate(i32),
+        #[handler(handle_zero)]
+        Zero,
+    }
+
+    impl CollectingPipeline {
+        fn handle_double(v: i32) -> i32 {
+            v * 2
+        }
+
+        fn handle_negate(v: i32) -> i32 {
+            -v
+        }
+
+        fn handle_zero() -> i32 {
+            0
+        }
+    }
+
+    #[test]
+    fn collecting_pipeline_works() {
+        let pipeline: PipelineVec<_> = vec![
+            CollectingPipeline::Double(4),
+            CollectingPipeline::Zero,
+            CollectingPipeline::Negate(3),
+        ]
+        .into();
+
+        assert_eq!(vec![8, 0, -3], pipeline.execute_collect());
+    }
+
+    #[test]
+    fn pipeline_execute_rev_works() {
+        let pipeline = vec![RecordingStep(0), RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.execute_rev();
+
+        assert_eq!(vec![2, 1, 0], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_with_execute_rev_works() {
+        let pipeline = vec![
+            NamedFieldPipeline::Resize {
+                width: 0,
+                height: 0,
+            },
+            NamedFieldPipeline::Resize {
+                width: 1,
+                height: 1,
+            },
+            NamedFieldPipeline::Resize {
+                width: 2,
+                height: 2,
+            },
+        ]
+        .into_pipeline();
+
+        let mut sizes = vec![];
+        pipeline.execute_rev_mut(&mut sizes);
+
+        assert_eq!(vec![(2, 2), (1, 1), (0, 0)], sizes);
+
+        let pipeline = vec![
+            DerivedRefDataPipeline::One(1.0),
+            DerivedRefDataPipeline::Two,
+        ]
+        .into_pipeline();
+
+        let data = DerivedRefDataPipelineData {
+            mult: 3.0,
+            one_value: Cell::new(0.0),
+            two_count: Cell::new(0),
+        };
+        pipeline.execute_rev(&data);
+
+        assert_eq!(3.0, data.one_value.get());
+        assert_eq!(1, data.two_count.get());
+    }
+
+    #[test]
+    fn pipeline_builder_works() {
+        let include_last = true;
+
+        let mut builder = PipelineBuilder::with_capacity(3)
+            .step(RecordingStep(1))
+            .step(RecordingStep(2));
+        if include_last {
+            builder = builder.step(RecordingStep(3));
+        }
+        let pipeline = builder.build();
+
+        assert_eq!(3, pipeline.len());
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    fn nested_pipeline() -> PipelineVec<PipelineVec<RecordingStep>> {
+        vec![
+            vec![RecordingStep(1), RecordingStep(2)].into_pipeline(),
+            Vec::<RecordingStep>::new().into_pipeline(),
+            vec![RecordingStep(3)].into_pipeline(),
+        ]
+        .into_pipeline()
+    }
+
+    #[test]
+    fn nested_pipeline_works() {
+        nested_pipeline().execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    fn flattened_pipeline_matches_nested() {
+        nested_pipeline().execute();
+        let nested = recorded_steps();
+
+        RECORDED_STEPS.with(|recorded| recorded.borrow_mut().clear());
+
+        let flat = nested_pipeline().flatten();
+
+        assert_eq!(3, flat.len());
+
+        flat.execute();
+
+        assert_eq!(nested, recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_append_works() {
+        let mut pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.append(vec![RecordingStep(3)].into_pipeline());
+
+        assert_eq!(3, pipeline.len());
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_concat_works() {
+        let first = vec![RecordingStep(1)].into_pipeline();
+        let second = vec![RecordingStep(2), RecordingStep(3)].into_pipeline();
+        let third = vec![RecordingStep(4)].into_pipeline();
+
+        (first.concat(second) + third).execute();
+
+        assert_eq!(vec![1, 2, 3, 4], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_execute_with_hooks_works() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+        let calls = RefCell::new(vec![]);
+
+        pipeline.execute_with_hooks(
+            |index| calls.borrow_mut().push(("before", index)),
+            |index| calls.borrow_mut().push(("after", index)),
+        );
+
+        assert_eq!(
+            vec![("before", 0), ("after", 0), ("before", 1), ("after", 1)],
+            calls.into_inner()
+        );
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pipeline_execute_timed_works() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2), RecordingStep(3)].into_pipeline();
+
+        let durations = pipeline.execute_timed();
+
+        assert_eq!(3, durations.len());
+        assert!(durations.iter().all(|d| d.as_secs_f64().is_finite()));
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[execute_with_mut(FilteredPipelineData)]
+    enum FilteredPipeline {
+        #[handler(handle_record)]
+        Record(u32),
+        #[handler(handle_disable)]
+        Disable,
+    }
+
+    #[derive(Default)]
+    struct FilteredPipelineData {
+        disabled: bool,
+        recorded: Vec<u32>,
+    }
+
+    impl FilteredPipeline {
+        fn handle_record(v: u32, arg: &mut FilteredPipelineData) {
+            arg.recorded.push(v);
+        }
+
+        fn handle_disable(arg: &mut FilteredPipelineData) {
+            arg.disabled = true;
+        }
+    }
+
+    #[test]
+    fn pipeline_with_execute_filtered_works() {
+        let pipeline = vec![
+            DerivedRefDataPipeline::One(1.0),
+            DerivedRefDataPipeline::Two,
+            DerivedRefDataPipeline::One(2.0),
+        ]
+        .into_pipeline();
+
+        let data = DerivedRefDataPipelineData {
+            mult: 1.0,
+            one_value: Cell::new(0.0),
+            two_count: Cell::new(0),
+        };
+        pipeline.execute_filtered(&data, |data| data.two_count.get() == 0);
+
+        assert_eq!(1.0, data.one_value.get());
+        assert_eq!(1, data.two_count.get());
+    }
+
+    #[test]
+    fn pipeline_with_execute_filtered_mut_works() {
+        let pipeline = vec![
+            FilteredPipeline::Record(1),
+            FilteredPipeline::Disable,
+            FilteredPipeline::Record(2),
+            FilteredPipeline::Record(3),
+        ]
+        .into_pipeline();
+
+        let mut data = FilteredPipelineData::default();
+        pipeline.execute_filtered_mut(&mut data, |data| !data.disabled);
+
+        assert_eq!(vec![1], data.recorded);
+    }
+
+    // the skipped fields would be read by other pipelines sharing the enum
+    #[allow(dead_code)]
+    #[derive(Execute)]
+    #[execute_with_mut(Vec<u32>)]
+    enum SkippingPipeline {
+        #[handler(handle_record)]
+        Record(u32),
+        #[skip]
+        Ignored(u32),
+        #[skip]
+        IgnoredNamed { value: u32 },
+        #[skip]
+        IgnoredUnit,
+    }
+
+    impl SkippingPipeline {
+        fn handle_record(v: u32, arg: &mut Vec<u32>) {
+            arg.push(v);
+        }
+    }
+
+    #[test]
+    fn skipped_variants_do_nothing() {
+        let pipeline = vec![
+            SkippingPipeline::Record(1),
+            SkippingPipeline::Ignored(2),
+            SkippingPipeline::IgnoredNamed { value: 3 },
+            SkippingPipeline::IgnoredUnit,
+            SkippingPipeline::Record(4),
+        ]
+        .into_pipeline();
+
+        let mut recorded = vec![];
+        pipeline.execute(&mut recorded);
+
+        assert_eq!(vec![1, 4], recorded);
+    }
+
+    #[derive(Execute)]
+    #[execute_with_mut(Vec<T>)]
+    enum GenericPipeline<T> {
+        #[handler(handle_push)]
+        Push(T),
+        #[handler(handle_pop)]
+        Pop,
+    }
+
+    impl<T> GenericPipeline<T> {
+        fn handle_push(v: T, arg: &mut Vec<T>) {
+            arg.push(v);
+        }
+
+        fn handle_pop(arg: &mut Vec<T>) {
+            arg.pop();
+        }
+    }
+
+    #[test]
+    fn generic_pipeline_works() {
+        let pipeline = vec![
+            GenericPipeline::Push("a"),
+            GenericPipeline::Push("b"),
+            GenericPipeline::Pop,
+            GenericPipeline::Push("c"),
+        ]
+        .into_pipeline();
+
+        let mut stack = vec![];
+        pipeline.execute(&mut stack);
+
+        assert_eq!(vec!["a", "c"], stack);
+    }
+
+    // deliberately not `Clone`, to show that by-reference handlers can share fields
+    struct Document(String);
+
+    #[derive(Execute)]
+    #[execute_with_mut(Vec<String>)]
+    enum RefHandlerPipeline {
+        #[handler(ref handle_len)]
+        #[handler(ref handle_upper)]
+        Text(String),
+        #[handler(ref handle_title)]
+        #[handler(handle_consume)]
+        Doc(Document),
+    }
+
+    impl RefHandlerPipeline {
+        fn handle_len(v: &str, arg: &mut Vec<String>) {
+            arg.push(v.len().to_string());
+        }
+
+        fn handle_upper(v: &str, arg: &mut Vec<String>) {
+            arg.push(v.to_uppercase());
+        }
+
+        fn handle_title(v: &Document, arg: &mut Vec<String>) {
+            arg.push(format!("title {}", v.0));
+        }
+
+        fn handle_consume(v: Document, arg: &mut Vec<String>) {
+            arg.push(format!("consumed {}", v.0));
+        }
+    }
+
+    #[test]
+    fn ref_handler_pipeline_works() {
+        let pipeline = vec![
+            RefHandlerPipeline::Text("hello".to_string()),
+            RefHandlerPipeline::Doc(Document("readme".to_string())),
+        ]
+        .into_pipeline();
+
+        let mut log = vec![];
+        pipeline.execute(&mut log);
+
+        assert_eq!(vec!["5", "HELLO", "title readme", "consumed readme"], log);
+    }
+
+    struct ValidationStep(Option<&'static str>);
+
+    impl TryExecute for ValidationStep {
+        type Error = &'static str;
+
+        fn try_execute(self) -> Result<(), Self::Error> {
+            match self.0 {
+                Some(problem) => Err(problem),
+                None => Ok(()),
+            }
+        }
+    }
+
+    #[test]
+    fn pipeline_execute_collect_errors_works() {
+        let pipeline: PipelineVec<_> = vec![
+            ValidationStep(Some("missing name")),
+            ValidationStep(None),
+            ValidationStep(Some("missing size")),
+        ]
+        .into();
+
+        assert_eq!(
+            vec![(0, "missing name"), (2, "missing size")],
+            pipeline.execute_collect_errors()
+        );
+    }
+
+    #[test]
+    fn pipeline_from_iterator_works() {
+        let pipeline = (1..=3).map(RecordingStep).collect::<PipelineVec<_>>();
+
+        assert_eq!(3, pipeline.len());
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_extend_works() {
+        let mut pipeline = vec![RecordingStep(1)].into_pipeline();
+
+        pipeline.extend((2..=3).map(RecordingStep));
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    trait Render {
+        fn render(self);
+    }
+
+    #[derive(Execute)]
+    #[pipeline(trait = "Render", method = "render")]
+    enum RenderPipeline {
+        #[handler(render_text)]
+        Text(String),
+        #[handler(render_rule)]
+        Rule,
+    }
+
+    static RENDERED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl RenderPipeline {
+        fn render_text(text: String) {
+            RENDERED.lock().unwrap().push(text);
+        }
+
+        fn render_rule() {
+            RENDERED.lock().unwrap().push("---".to_string());
+        }
+    }
+
+    #[test]
+    fn custom_trait_and_method_dispatch() {
+        for step in [
+            RenderPipeline::Text("title".to_string()),
+            RenderPipeline::Rule,
+            RenderPipeline::Text("body".to_string()),
+        ] {
+            step.render();
+        }
+
+        assert_eq!(vec!["title", "---", "body"], *RENDERED.lock().unwrap());
+    }
+
+    #[test]
+    fn pipeline_clone_and_debug_work() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+        let copy = pipeline.clone();
+
+        assert_eq!(
+            "PipelineVec { steps: [RecordingStep(1), RecordingStep(2)] }",
+            format!("{:?}", pipeline)
+        );
+
+        pipeline.execute();
+        copy.execute();
+        assert_eq!(vec![1, 2, 1, 2], recorded_steps());
+
+        // MutDataPipelineData is neither Clone nor Debug
+        let pipeline_with = vec![
+            DerivedMutDataPipeline::One(2.0),
+            DerivedMutDataPipeline::Two,
+        ]
+        .into_pipeline();
+        let copy_with = pipeline_with.clone();
+
+        assert_eq!(
+            "PipelineVecWith { steps: [One(2.0), Two] }",
+            format!("{:?}", pipeline_with)
+        );
+
+        let mut data = MutDataPipelineData::default();
+        pipeline_with.execute(&mut data);
+        copy_with.execute(&mut data);
+        assert_eq!(4.0, data.one_value);
+        assert_eq!(2, data.two_count);
+    }
+
+    #[test]
+    fn pipeline_step_access_works() {
+        let mut pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        assert_eq!(RecordingStep(2), pipeline[1]);
+        assert_eq!(Some(&RecordingStep(1)), pipeline.get(0));
+        assert_eq!(None, pipeline.get(2));
+        assert_eq!(None, pipeline.get_mut(2));
+
+        pipeline.get_mut(0).unwrap().0 = 10;
+        pipeline.execute();
+
+        assert_eq!(vec![10, 2], recorded_steps());
+    }
+
+    #[test]
+    #[should_panic]
+    fn pipeline_index_out_of_range_panics() {
+        let pipeline = vec![RecordingStep(1)].into_pipeline();
+
+        let _ = &pipeline[1];
+    }
+
+    #[test]
+    fn pipeline_insert_and_remove_work() {
+        let mut pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.insert(1, RecordingStep(10));
+        pipeline.insert(3, RecordingStep(20));
+        assert_eq!(RecordingStep(20), pipeline.remove(3));
+        pipeline.execute();
+
+        assert_eq!(vec![1, 10, 2], recorded_steps());
+    }
+
+    #[test]
+    #[should_panic]
+    fn pipeline_remove_out_of_range_panics() {
+        let mut pipeline = vec![RecordingStep(1)].into_pipeline();
+
+        pipeline.remove(1);
+    }
+
+    // a step wrapping an arbitrary closure, used to decorate other steps
+    struct ClosureStep(Box<dyn FnOnce()>);
+
+    impl Execute for ClosureStep {
+        fn execute(self) {
+            (self.0)()
+        }
+    }
+
+    #[test]
+    fn pipeline_map_steps_works() {
+        let pipeline = vec![RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        let decorated: PipelineVec<ClosureStep> = pipeline.map_steps(|step| {
+            ClosureStep(Box::new(move || {
+                RecordingStep(step.0 * 100).execute();
+                step.execute();
+            }))
+        });
+        decorated.execute();
+
+        assert_eq!(vec![100, 1, 200, 2], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_retain_works() {
+        let mut pipeline: PipelineVec<_> = (1..=6).map(RecordingStep).collect();
+
+        pipeline.retain(|step| step.0 % 2 == 0);
+        pipeline.execute();
+
+        assert_eq!(vec![2, 4, 6], recorded_steps());
+    }
+
+    struct ScaleConfig {
+        factor: u32,
+    }
+
+    enum ScalePipeline {
+        Add(u32),
+        Double,
+    }
+
+    impl ExecuteWith2<ScaleConfig, Vec<u32>> for ScalePipeline {
+        fn execute(self, a1: &ScaleConfig, a2: &mut Vec<u32>) {
+            match self {
+                ScalePipeline::Add(v) => a2.push(v * a1.factor),
+                ScalePipeline::Double => {
+                    let last = a2.last().copied().unwrap_or_default();
+                    a2.push(last * 2)
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn two_arg_pipeline_works() {
+        let pipeline = vec![
+            ScalePipeline::Add(1),
+            ScalePipeline::Double,
+            ScalePipeline::Add(5),
+        ]
+        .into_pipeline();
+
+        let config = ScaleConfig { factor: 3 };
+        let mut output = Vec::new();
+        pipeline.execute(&config, &mut output);
+
+        assert_eq!(vec![3, 6, 15], output);
+    }
+
+    #[test]
+    fn mut_data_pipeline_fold_works() {
+        let pipeline = vec![
+            DerivedMutDataPipeline::One(12.0),
+            DerivedMutDataPipeline::Two,
+            DerivedMutDataPipeline::One(3.0),
+        ]
+        .into_pipeline();
+
+        let data = pipeline.execute_fold(MutDataPipelineData {
+            one_value: 5.0,
+            two_count: 0,
+        });
+
+        assert_eq!(20.0, data.one_value);
+        assert_eq!(1, data.two_count);
+    }
+
+    static CLOSURE_HANDLED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    #[derive(Execute)]
+    enum ClosureHandlerPipeline {
+        #[handler(|v: f32, w: f32| CLOSURE_HANDLED.lock().unwrap().push(format!("{}", v * w)))]
+        Scale(f32, f32),
+        #[handler(ref |name: &String| CLOSURE_HANDLED.lock().unwrap().push(name.clone()))]
+        Name(String),
+        #[handler(|| CLOSURE_HANDLED.lock().unwrap().push("unit".to_string()))]
+        Unit,
+    }
+
+    #[test]
+    fn closure_handler_works() {
+        let pipeline = vec![
+            ClosureHandlerPipeline::Scale(2.0, 3.0),
+            ClosureHandlerPipeline::Name("named".to_string()),
+            ClosureHandlerPipeline::Unit,
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec!["6", "named", "unit"], *CLOSURE_HANDLED.lock().unwrap());
+    }
+
+    enum DescribedPipeline {
+        Allocate(f32, f32),
+        Init,
+        Run(f32),
+    }
+
+    impl Describe for DescribedPipeline {
+        fn describe(&self) -> String {
+            match self {
+                DescribedPipeline::Allocate(x, y) => format!("Allocate {}x{}", x, y),
+                DescribedPipeline::Init => "Init".to_string(),
+                DescribedPipeline::Run(delta) => format!("Run {}", delta),
+            }
+        }
+    }
+
+    #[test]
+    fn pipeline_describe_works() {
+        let pipeline: PipelineVec<_> = vec![
+            DescribedPipeline::Init,
+            DescribedPipeline::Allocate(2.0, 3.0),
+            DescribedPipeline::Run(1.5),
+        ]
+        .into();
+
+        assert_eq!(vec!["Init", "Allocate 2x3", "Run 1.5"], pipeline.describe());
+    }
+
+    // the fields would be read by the handlers of an `Execute` pipeline sharing the enum
+    #[allow(dead_code)]
+    #[derive(Describe)]
+    enum DerivedDescribedPipeline {
+        Allocate(f32, f32),
+        Init,
+        Resize { width: u32 },
+        Run(f32),
+    }
+
+    #[test]
+    fn derived_describe_works() {
+        let pipeline: PipelineVec<_> = vec![
+            DerivedDescribedPipeline::Init,
+            DerivedDescribedPipeline::Allocate(2.0, 3.0),
+            DerivedDescribedPipeline::Resize { width: 4 },
+            DerivedDescribedPipeline::Run(1.5),
+        ]
+        .into();
+
+        assert_eq!(
+            vec!["Init", "Allocate", "Resize", "Run"],
+            pipeline.describe()
+        );
+    }
+
+    // the fields would be read by the handlers of an `Execute` pipeline sharing the enum
+    #[allow(dead_code)]
+    #[derive(Labeled)]
+    enum LabeledPipeline {
+        #[label("Allocate GPU buffers")]
+        Allocate(f32, f32),
+        Init,
+        #[label("Run the simulation")]
+        Run {
+            delta: f32,
+        },
+    }
+
+    #[test]
+    fn derived_labeled_works() {
+        assert_eq!(
+            "Allocate GPU buffers",
+            LabeledPipeline::Allocate(1.0, 1.0).label()
+        );
+        assert_eq!("Init", LabeledPipeline::Init.label());
+        assert_eq!(
+            "Run the simulation",
+            LabeledPipeline::Run { delta: 1.0 }.label()
+        );
+    }
+
+    // the fields would be read by the handlers of an `Execute` pipeline sharing the enum
+    #[allow(dead_code)]
+    #[derive(VariantIndex)]
+    enum IndexedVariantPipeline {
+        Allocate(f32, f32),
+        Init,
+        Run { delta: f32 },
+    }
+
+    #[test]
+    fn derived_variant_index_works() {
+        assert_eq!(3, IndexedVariantPipeline::VARIANT_COUNT);
+        assert_eq!(
+            0,
+            IndexedVariantPipeline::Allocate(1.0, 1.0).variant_index()
+        );
+        assert_eq!(1, IndexedVariantPipeline::Init.variant_index());
+        assert_eq!(
+            2,
+            IndexedVariantPipeline::Run { delta: 1.0 }.variant_index()
+        );
+    }
+
+    #[test]
+    fn pipeline_execute_until_works() {
+        let pipeline: PipelineVec<_> = (1..=5).map(RecordingStep).collect();
+
+        assert_eq!(2, pipeline.execute_until(|step| step.0 == 3));
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_execute_cancellable_works() {
+        static CANCEL: AtomicBool = AtomicBool::new(false);
+
+        struct CancellingStep(u32);
+
+        impl Execute for CancellingStep {
+            fn execute(self) {
+                RecordingStep(self.0).execute();
+                CANCEL.store(true, Ordering::SeqCst);
+            }
+        }
+
+        let pipeline: PipelineVec<_> = (1..=3).map(CancellingStep).collect();
+
+        pipeline.execute_cancellable(&CANCEL);
+
+        assert_eq!(vec![1], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_partition_works() {
+        let pipeline: PipelineVec<_> = vec![5, 2, 4, 1, 6, 3]
+            .into_iter()
+            .map(RecordingStep)
+            .collect();
+
+        let (small, large) = pipeline.partition(|step| step.0 <= 3);
+
+        assert_eq!(
+            vec![&RecordingStep(2), &RecordingStep(1), &RecordingStep(3)],
+            small.iter().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![&RecordingStep(5), &RecordingStep(4), &RecordingStep(6)],
+            large.iter().collect::<Vec<_>>()
+        );
+    }
+
+    // a type unrelated to the enum, whose associated functions are used as handlers
+    struct ExternalService;
+
+    static EXTERNAL_PROCESSED: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl ExternalService {
+        fn process(job: String) {
+            EXTERNAL_PROCESSED.lock().unwrap().push(job);
+        }
+
+        fn finish() {
+            EXTERNAL_PROCESSED
+                .lock()
+                .unwrap()
+                .push("finished".to_string());
+        }
+    }
+
+    #[derive(Execute)]
+    enum ExternalHandlerPipeline {
+        #[handler(ExternalService::process)]
+        Process(String),
+        #[handler(crate::tests::ExternalService::finish)]
+        Finish,
+    }
+
+    #[test]
+    fn external_type_handler_works() {
+        let pipeline = vec![
+            ExternalHandlerPipeline::Process("job".to_string()),
+            ExternalHandlerPipeline::Finish,
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec!["job", "finished"], *EXTERNAL_PROCESSED.lock().unwrap());
+    }
+
+    #[test]
+    fn pipeline_first_and_last_work() {
+        let pipeline: PipelineVec<_> = (1..=3).map(RecordingStep).collect();
+
+        assert_eq!(Some(&RecordingStep(1)), pipeline.first());
+        assert_eq!(Some(&RecordingStep(3)), pipeline.last());
+
+        let empty: PipelineVec<RecordingStep> = PipelineVec::new();
+
+        assert_eq!(None, empty.first());
+        assert_eq!(None, empty.last());
+    }
+
+    #[test]
+    fn pipeline_clear_and_truncate_work() {
+        let mut pipeline: PipelineVec<_> = (1..=3).map(RecordingStep).collect();
+
+        pipeline.clear();
+        assert!(pipeline.is_empty());
+
+        pipeline.push(RecordingStep(4));
+        pipeline.push(RecordingStep(5));
+        pipeline.truncate(1);
+        pipeline.execute();
+
+        assert_eq!(vec![4], recorded_steps());
+    }
+
+    #[test]
+    fn empty_pipelines_do_nothing() {
+        let pipeline: PipelineVec<RecordingStep> = PipelineVec::default();
+
+        assert_eq!(0, pipeline.len());
+        pipeline.execute();
+        assert!(recorded_steps().is_empty());
+
+        let mut pipeline_with: PipelineVecWith<DerivedMutDataPipeline, MutDataPipelineData> =
+            PipelineVecWith::default();
+        assert_eq!(0, pipeline_with.len());
+
+        pipeline_with.push(DerivedMutDataPipeline::Two);
+        let mut data = MutDataPipelineData::default();
+        pipeline_with.execute(&mut data);
+        assert_eq!(1, data.two_count);
+
+        let mut pipeline = PipelineVec::new();
+        pipeline.push(RecordingStep(1));
+        pipeline.execute();
+        assert_eq!(vec![1], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[handler_prefix("handle_")]
+    enum PrefixedPipeline {
+        Load(String),
+        ApplyHTTPConfig {
+            retries: u32,
+        },
+        #[handler(handle_override)]
+        Save,
+    }
+
+    static PREFIXED_CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl PrefixedPipeline {
+        fn handle_load(path: String) {
+            PREFIXED_CALLS
+                .lock()
+                .unwrap()
+                .push(format!("load {}", path));
+        }
+
+        fn handle_apply_http_config(retries: u32) {
+            PREFIXED_CALLS
+                .lock()
+                .unwrap()
+                .push(format!("config {}", retries));
+        }
+
+        fn handle_override() {
+            PREFIXED_CALLS.lock().unwrap().push("save".to_string());
+        }
+    }
+
+    #[test]
+    fn handler_prefix_works() {
+        let pipeline = vec![
+            PrefixedPipeline::Load("a.txt".to_string()),
+            PrefixedPipeline::ApplyHTTPConfig { retries: 3 },
+            PrefixedPipeline::Save,
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(
+            vec!["load a.txt", "config 3", "save"],
+            *PREFIXED_CALLS.lock().unwrap()
+        );
+    }
+
+    #[derive(Execute)]
+    #[handler_prefix("")]
+    enum UnprefixedPipeline {
+        Move(u32),
+        Run(u32),
+    }
+
+    impl UnprefixedPipeline {
+        fn r#move(v: u32) {
+            RecordingStep(v).execute()
+        }
+
+        fn run(v: u32) {
+            RecordingStep(v * 10).execute()
+        }
+    }
+
+    #[test]
+    fn empty_handler_prefix_works() {
+        let pipeline =
+            vec![UnprefixedPipeline::Move(1), UnprefixedPipeline::Run(2)].into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 20], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_execute_counting_works() {
+        let pipeline: PipelineVec<_> = (1..=4).map(RecordingStep).collect();
+        assert_eq!(4, pipeline.execute_counting());
+
+        let pipeline: PipelineVec<_> = (5..=8).map(RecordingStep).collect();
+        assert_eq!(1, pipeline.execute_until(|step| step.0 > 5));
+
+        assert_eq!(vec![1, 2, 3, 4, 5], recorded_steps());
+    }
+
+    const LATE_PRIORITY: i32 = -5;
+
+    #[derive(Execute, Labeled, Prioritized)]
+    enum PrioritizedPipeline {
+        #[handler(crate::tests::record)]
+        #[priority(10)]
+        Init(u32),
+        #[handler(crate::tests::record)]
+        Run(u32),
+        #[handler(crate::tests::record)]
+        #[priority(LATE_PRIORITY)]
+        Teardown(u32),
+    }
+
+    #[test]
+    fn pipeline_execute_by_priority_works() {
+        let pipeline = vec![
+            PrioritizedPipeline::Teardown(1),
+            PrioritizedPipeline::Run(2),
+            PrioritizedPipeline::Init(3),
+            PrioritizedPipeline::Run(4),
+            PrioritizedPipeline::Init(5),
+        ]
+        .into_pipeline();
+
+        assert_eq!(10, PrioritizedPipeline::Init(0).priority());
+        assert_eq!(0, PrioritizedPipeline::Run(0).priority());
+
+        pipeline.execute_by_priority();
+
+        assert_eq!(vec![3, 5, 2, 4, 1], recorded_steps());
+    }
+
+    #[derive(Debug)]
+    struct BoundedStep(u32);
+
+    impl Execute for BoundedStep {
+        fn execute(self) {
+            RecordingStep(self.0).execute()
+        }
+    }
+
+    impl Validate for BoundedStep {
+        fn validate(&self) -> Result<(), String> {
+            match self.0 <= 10 {
+                true => Ok(()),
+                false => Err(format!("{} is more than 10", self.0)),
+            }
+        }
+    }
+
+    #[test]
+    fn try_into_pipeline_validates_steps() {
+        let res = vec![BoundedStep(1), BoundedStep(20), BoundedStep(30)].try_into_pipeline();
+
+        assert_eq!(
+            BuildError {
+                index: 1,
+                message: "20 is more than 10".to_string()
+            },
+            res.unwrap_err()
+        );
+
+        let pipeline = vec![BoundedStep(1), BoundedStep(2)]
+            .try_into_pipeline()
+            .unwrap();
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_slice_works() {
+        let steps = vec![RecordingStep(1), RecordingStep(2)];
+
+        PipelineSlice::new(&steps).execute();
+        PipelineSlice::from(&steps[1..]).execute();
+
+        assert_eq!(vec![1, 2, 2], recorded_steps());
+
+        steps.into_pipeline().execute();
+
+        assert_eq!(vec![1, 2, 2, 1, 2], recorded_steps());
+    }
+
+    #[derive(Execute, Describe)]
+    enum GatedPipeline {
+        #[handler(crate::tests::record)]
+        Always(u32),
+        // only compiled with the serde feature, along with its handler
+        #[cfg(feature = "serde")]
+        #[handler(record_gated)]
+        Gated(u32),
+    }
+
+    impl GatedPipeline {
+        #[cfg(feature = "serde")]
+        fn record_gated(v: u32) {
+            RecordingStep(v * 10).execute()
+        }
+    }
+
+    #[test]
+    fn cfg_gated_variant_works() {
+        let mut pipeline = vec![GatedPipeline::Always(1)].into_pipeline();
+        #[cfg(feature = "serde")]
+        pipeline.push(GatedPipeline::Gated(2));
+        pipeline.push(GatedPipeline::Always(3));
+
+        #[cfg(feature = "serde")]
+        assert_eq!(vec!["Always", "Gated", "Always"], pipeline.describe());
+        #[cfg(not(feature = "serde"))]
+        assert_eq!(vec!["Always", "Always"], pipeline.describe());
+
+        pipeline.execute();
+
+        #[cfg(feature = "serde")]
+        assert_eq!(vec![1, 20, 3], recorded_steps());
+        #[cfg(not(feature = "serde"))]
+        assert_eq!(vec![1, 3], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[execute_indexed]
+    enum IndexedPipeline {
+        #[handler(with_index record_index)]
+        Progress(String),
+        #[handler(record_unindexed)]
+        Quiet,
+    }
+
+    static INDEXED_CALLS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    impl IndexedPipeline {
+        fn record_index(name: String, index: usize) {
+            INDEXED_CALLS
+                .lock()
+                .unwrap()
+                .push(format!("{} {}", name, index));
+        }
+
+        fn record_unindexed() {
+            INDEXED_CALLS.lock().unwrap().push("quiet".to_string());
+        }
+    }
+
+    #[test]
+    fn indexed_pipeline_works() {
+        let pipeline: PipelineVec<_> = vec![
+            IndexedPipeline::Progress("a".to_string()),
+            IndexedPipeline::Quiet,
+            IndexedPipeline::Progress("c".to_string()),
+        ]
+        .into();
+
+        pipeline.execute_indexed();
+
+        assert_eq!(vec!["a 0", "quiet", "c 2"], *INDEXED_CALLS.lock().unwrap());
+    }
+
+    #[test]
+    fn nested_indexed_pipeline_works() {
+        static NESTED_CALLS: Mutex<Vec<(u32, usize)>> = Mutex::new(Vec::new());
+
+        struct NestedStep(u32);
+
+        impl ExecuteIndexed for NestedStep {
+            fn execute_indexed(self, index: usize) {
+                NESTED_CALLS.lock().unwrap().push((self.0, index));
+            }
+        }
+
+        let pipeline: PipelineVec<PipelineVec<_>> = vec![
+            vec![NestedStep(1), NestedStep(2)].into(),
+            vec![NestedStep(3)].into(),
+        ]
+        .into();
+
+        pipeline.execute_indexed();
+
+        assert_eq!(vec![(1, 0), (2, 1), (3, 0)], *NESTED_CALLS.lock().unwrap());
+    }
+
+    #[test]
+    fn pipeline_dedup_works() {
+        let mut pipeline: PipelineVec<_> = vec![1, 1, 2, 2, 2, 1, 3]
+            .into_iter()
+            .map(RecordingStep)
+            .collect();
+
+        pipeline.dedup();
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 1, 3], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_deque_works() {
+        let mut pipeline = vec![RecordingStep(2), RecordingStep(3)].into_pipeline_deque();
+
+        pipeline.push_front(RecordingStep(1));
+        pipeline.push_back(RecordingStep(4));
+        pipeline.push_front(RecordingStep(0));
+        assert_eq!(Some(RecordingStep(0)), pipeline.pop_front());
+        assert_eq!(4, pipeline.len());
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3, 4], recorded_steps());
+    }
+
+    struct Limits {
+        max: u32,
+    }
+
+    enum LimitedPipeline {
+        Check(u32),
+    }
+
+    impl TryExecuteWith<Limits> for LimitedPipeline {
+        type Error = String;
+
+        fn try_execute(self, arg: &Limits) -> Result<(), String> {
+            match self {
+                LimitedPipeline::Check(v) if v > arg.max => {
+                    Err(format!("{} exceeds {}", v, arg.max))
+                }
+                LimitedPipeline::Check(v) => {
+                    RecordingStep(v).execute();
+                    Ok(())
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn pipeline_execute_with_try_works() {
+        let steps = || {
+            PipelineVecWith::from(vec![
+                LimitedPipeline::Check(1),
+                LimitedPipeline::Check(5),
+                LimitedPipeline::Check(10),
+            ])
+        };
+
+        let res = steps().execute_with_try(&Limits { max: 20 });
+        assert_eq!(Ok(()), res);
+
+        let res = steps().execute_with_try(&Limits { max: 3 });
+        assert_eq!(Err((1, "5 exceeds 3".to_string())), res);
+
+        assert_eq!(vec![1, 5, 10, 1], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[execute_with_mut(Vec<String>)]
+    enum ReorderedPipeline {
+        #[handler(describe_move; 2, 1)]
+        Move(u32, String),
+        #[handler(describe_name; 2)]
+        Rename { id: u32, name: String },
+    }
+
+    impl ReorderedPipeline {
+        fn describe_move(name: String, distance: u32, arg: &mut Vec<String>) {
+            arg.push(format!("{} moved {}", name, distance));
+        }
+
+        fn describe_name(name: String, arg: &mut Vec<String>) {
+            arg.push(format!("renamed to {}", name));
+        }
+    }
+
+    #[test]
+    fn reordered_handler_fields_work() {
+        let pipeline = vec![
+            ReorderedPipeline::Move(3, "player".to_string()),
+            ReorderedPipeline::Rename {
+                id: 1,
+                name: "hero".to_string(),
+            },
+        ]
+        .into_pipeline();
+
+        let mut output = Vec::new();
+        pipeline.execute(&mut output);
+
+        assert_eq!(vec!["player moved 3", "renamed to hero"], output);
+    }
+
+    #[test]
+    fn boxed_pipeline_mixes_step_types() {
+        let pipeline = vec![
+            Box::new(RecordingStep(1)) as Box<dyn BoxExecute>,
+            Box::new(PrioritizedPipeline::Run(2)),
+            Box::new(vec![RecordingStep(3), RecordingStep(4)].into_pipeline()),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3, 4], recorded_steps());
+    }
+
+    // a step that panics instead of recording when its value is zero
+    #[cfg(feature = "std")]
+    struct PanickingStep(u32);
+
+    #[cfg(feature = "std")]
+    impl Execute for PanickingStep {
+        fn execute(self) {
+            if self.0 == 0 {
+                panic!("step failed");
+            }
+            RecordingStep(self.0).execute()
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pipeline_execute_catch_works() {
+        let pipeline = vec![PanickingStep(1), PanickingStep(0), PanickingStep(3)].into_pipeline();
+
+        let res = pipeline.execute_catch();
+
+        assert_eq!(3, res.len());
+        assert!(res[0].is_ok());
+        assert_eq!(
+            Some(&"step failed"),
+            res[1].as_ref().unwrap_err().downcast_ref::<&str>()
+        );
+        assert!(res[2].is_ok());
+        assert_eq!(vec![1, 3], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[execute_with(u32)]
+    enum ScaledPipeline {
+        #[handler(record_scaled)]
+        Record(u32),
+    }
+
+    impl ScaledPipeline {
+        fn record_scaled(v: u32, factor: &u32) {
+            RecordingStep(v * factor).execute()
+        }
+    }
+
+    #[test]
+    fn pipeline_with_bind_works() {
+        let pipeline: PipelineVecWith<_, u32> =
+            vec![ScaledPipeline::Record(1), ScaledPipeline::Record(2)].into_pipeline();
+
+        let bound: PipelineVec<Bound<ScaledPipeline, u32>> = pipeline.bind(10);
+        bound.execute();
+
+        assert_eq!(vec![10, 20], recorded_steps());
+    }
+
+    trait Plugin {
+        fn run(&self) -> u32;
+    }
+
+    struct DoublingPlugin(u32);
+
+    impl Plugin for DoublingPlugin {
+        fn run(&self) -> u32 {
+            self.0 * 2
+        }
+    }
+
+    #[derive(Execute)]
+    enum PluginPipeline {
+        #[handler(run_plugin)]
+        Plugin(Box<dyn Plugin>),
+    }
+
+    impl PluginPipeline {
+        fn run_plugin(plugin: Box<dyn Plugin>) {
+            RecordingStep(plugin.run()).execute()
+        }
+    }
+
+    #[test]
+    fn boxed_field_handler_works() {
+        let pipeline = vec![
+            PluginPipeline::Plugin(Box::new(DoublingPlugin(1))),
+            PluginPipeline::Plugin(Box::new(DoublingPlugin(5))),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![2, 10], recorded_steps());
+    }
+
+    #[test]
+    fn run_functions_work() {
+        run([RecordingStep(1), RecordingStep(2)]);
+        run((3..=4).map(RecordingStep));
+
+        assert_eq!(vec![1, 2, 3, 4], recorded_steps());
+
+        run_with([ScaledPipeline::Record(1), ScaledPipeline::Record(2)], &100);
+
+        assert_eq!(vec![1, 2, 3, 4, 100, 200], recorded_steps());
+
+        let mut data = MutDataPipelineData::default();
+        run_with_mut(
+            vec![
+                DerivedMutDataPipeline::One(2.0),
+                DerivedMutDataPipeline::Two,
+            ],
+            &mut data,
+        );
+
+        assert_eq!(2.0, data.one_value);
+        assert_eq!(1, data.two_count);
+    }
+
+    // accepts any of the pipeline containers, but nothing else
+    fn step_type_name<P: Pipeline>(_pipeline: &P) -> &'static str {
+        std::any::type_name::<P::Step>()
+    }
+
+    #[test]
+    fn pipeline_containers_are_pipelines() {
+        let pipeline = vec![RecordingStep(1)].into_pipeline();
+        let deque = vec![RecordingStep(1)].into_pipeline_deque();
+
+        assert!(step_type_name(&pipeline).ends_with("RecordingStep"));
+        assert!(step_type_name(&deque).ends_with("RecordingStep"));
+        assert!(step_type_name(&PipelineSlice::new(&[1u8])).ends_with("u8"));
+    }
+
+    #[test]
+    fn pipeline_step_iter_works() {
+        let pipeline: PipelineVec<_> = (1..=3).map(RecordingStep).collect();
+        let mut steps = pipeline.into_step_iter();
+
+        assert_eq!(3, steps.len());
+        assert!(recorded_steps().is_empty());
+
+        assert_eq!(Some(()), steps.next());
+        assert_eq!(vec![1], recorded_steps());
+
+        assert_eq!(Some(()), steps.next());
+        assert_eq!(vec![1, 2], recorded_steps());
+
+        assert_eq!(1, steps.count());
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[derive(Execute, Constructors)]
+    enum ConstructedPipeline {
+        #[handler(crate::tests::record)]
+        Run(u32),
+        #[handler(record_sum)]
+        RunBoth { first: u32, second: u32 },
+        #[handler(record_zero)]
+        Reset,
+    }
+
+    impl ConstructedPipeline {
+        fn record_sum(first: u32, second: u32) {
+            RecordingStep(first + second).execute()
+        }
+
+        fn record_zero() {
+            RecordingStep(0).execute()
+        }
+    }
+
+    #[test]
+    fn derived_constructors_work() {
+        let pipeline = vec![
+            ConstructedPipeline::run(1),
+            ConstructedPipeline::run_both(2, 3),
+            ConstructedPipeline::reset(),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 5, 0], recorded_steps());
+    }
+
+    #[derive(Execute, Constructors)]
+    enum KeywordPipeline {
+        #[handler(crate::tests::record)]
+        Move(u32),
+        #[handler(record_zero)]
+        Return,
+    }
+
+    impl KeywordPipeline {
+        fn record_zero() {
+            RecordingStep(0).execute()
+        }
+    }
+
+    #[test]
+    fn derived_constructors_of_keyword_variants_work() {
+        let pipeline =
+            vec![KeywordPipeline::r#move(1), KeywordPipeline::r#return()].into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 0], recorded_steps());
+    }
+
+    #[derive(Debug, PartialEq)]
+    enum OrderedStep {
+        Init,
+        Run,
+        Report,
+    }
+
+    /// Init must come before Run, so a Run may never precede an Init.
+    fn init_before_run(earlier: &OrderedStep, later: &OrderedStep) -> bool {
+        !(*earlier == OrderedStep::Run && *later == OrderedStep::Init)
+    }
+
+    #[test]
+    fn pipeline_validate_order_works() {
+        let ordered: PipelineVec<_> =
+            vec![OrderedStep::Init, OrderedStep::Run, OrderedStep::Report].into();
+        assert_eq!(Ok(()), ordered.validate_order(init_before_run));
+
+        let misordered: PipelineVec<_> = vec![
+            OrderedStep::Report,
+            OrderedStep::Run,
+            OrderedStep::Run,
+            OrderedStep::Init,
+        ]
+        .into();
+        assert_eq!(Err((1, 3)), misordered.validate_order(init_before_run));
+
+        assert_eq!(Ok(()), PipelineVec::new().validate_order(init_before_run));
+    }
+
+    #[test]
+    fn pipeline_deref_to_slice_works() {
+        let pipeline: PipelineVec<_> = vec![3, 1, 2].into();
+
+        assert!(pipeline.contains(&1));
+        assert_eq!(Some(&3), pipeline.iter().max());
+        assert_eq!(Some((&3, &[1, 2][..])), pipeline.split_first());
+        assert_eq!(
+            vec![[3, 1], [1, 2]],
+            pipeline
+                .windows(2)
+                .map(|w| [w[0], w[1]])
+                .collect::<Vec<_>>()
+        );
+
+        let slice: &[u32] = pipeline.as_ref();
+        assert_eq!(&[3, 1, 2], slice);
+    }
+
+    #[derive(Execute, Debug, PartialEq)]
+    #[execute_in_place]
+    enum InPlacePipeline {
+        #[handler(mut increment)]
+        Count(u32),
+        #[handler(mut push_total; 2, 1)]
+        #[handler(ref record)]
+        Total { total: u32, history: Vec<u32> },
+        #[handler(crate::tests::record)]
+        Copied(u32),
+    }
+
+    impl InPlacePipeline {
+        fn increment(count: &mut u32) {
+            *count += 1
+        }
+
+        fn push_total(history: &mut Vec<u32>, total: &mut u32) {
+            *total += 10;
+            history.push(*total);
+        }
+
+        fn record(total: &u32, _history: &[u32]) {
+            RecordingStep(*total).execute()
+        }
+    }
+
+    #[test]
+    fn derived_in_place_works() {
+        let mut pipeline: PipelineVec<_> = vec![
+            InPlacePipeline::Count(0),
+            InPlacePipeline::Total {
+                total: 5,
+                history: Vec::new(),
+            },
+            InPlacePipeline::Copied(7),
+        ]
+        .into();
+
+        ExecuteInPlace::execute(&mut pipeline);
+        ExecuteInPlace::execute(&mut pipeline);
+
+        assert_eq!(vec![15, 7, 25, 7], recorded_steps());
+        assert_eq!(
+            &[
+                InPlacePipeline::Count(2),
+                InPlacePipeline::Total {
+                    total: 25,
+                    history: vec![15, 25],
+                },
+                InPlacePipeline::Copied(7),
+            ],
+            pipeline.as_ref()
+        );
+    }
+
+    #[derive(Execute, Labeled)]
+    enum TaggedPipeline {
+        #[handler(crate::tests::record)]
+        #[label("build")]
+        Build(u32),
+        #[handler(crate::tests::record)]
+        #[label("test")]
+        Test(u32),
+        #[handler(crate::tests::record)]
+        Deploy(u32),
+    }
+
+    #[test]
+    fn pipeline_execute_only_works() {
+        let pipeline = vec![
+            TaggedPipeline::Build(1),
+            TaggedPipeline::Test(2),
+            TaggedPipeline::Deploy(3),
+            TaggedPipeline::Build(4),
+        ]
+        .into_pipeline();
+
+        pipeline.execute_only(&["Deploy", "build"]);
+
+        assert_eq!(vec![1, 3, 4], recorded_steps());
+    }
+
+    /// Records a marker for each step, before running the step itself.
+    struct MarkingStrategy {
+        runs: Cell<u32>,
+    }
+
+    impl ExecutionStrategy for MarkingStrategy {
+        fn run<T: Execute>(&self, step: T) {
+            self.runs.set(self.runs.get() + 1);
+            RecordingStep(100 * self.runs.get()).execute();
+            step.execute()
+        }
+    }
+
+    #[test]
+    fn pipeline_execute_with_strategy_works() {
+        let pipeline: PipelineVec<_> = (1..=2).map(RecordingStep).collect();
+        pipeline.execute_with_strategy(&InlineStrategy);
+
+        assert_eq!(vec![1, 2], recorded_steps());
+
+        let strategy = MarkingStrategy { runs: Cell::new(0) };
+        let pipeline: PipelineVec<_> = (3..=4).map(RecordingStep).collect();
+        pipeline.execute_with_strategy(&strategy);
+
+        assert_eq!(2, strategy.runs.get());
+        assert_eq!(vec![1, 2, 100, 3, 200, 4], recorded_steps());
+    }
+
+    #[test]
+    fn array_into_pipeline_vec_works() {
+        let pipeline = [RecordingStep(1), RecordingStep(2)].into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    #[test]
+    fn slice_into_pipeline_vec_works() {
+        let steps = [RecordingStep(1), RecordingStep(2), RecordingStep(3)];
+        let pipeline = steps[1..].into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![2, 3], recorded_steps());
+        assert_eq!(3, steps.len());
+    }
+
+    #[test]
+    fn array_pipeline_works() {
+        let pipeline = [RecordingStep(1), RecordingStep(2), RecordingStep(3)].into_pipeline_array();
+
+        assert_eq!(3, pipeline.len());
+        assert!(!pipeline.is_empty());
+        assert_eq!(
+            "enum_pipeline::tests::RecordingStep",
+            step_type_name(&pipeline)
+        );
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+
+        let empty: PipelineArray<RecordingStep, 0> = [].into();
+        assert!(empty.is_empty());
+        empty.execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_info_works() {
+        let pipeline = vec![TaggedPipeline::Build(1), TaggedPipeline::Deploy(2)].into_pipeline();
+
+        assert_eq!(
+            PipelineInfo {
+                len: 2,
+                labels: vec![Some("build".to_string()), Some("Deploy".to_string())],
+                priorities: vec![None, None],
+            },
+            pipeline.info()
+        );
+
+        let pipeline =
+            vec![PrioritizedPipeline::Run(1), PrioritizedPipeline::Init(2)].into_pipeline();
+        let info = pipeline.info_with_priorities();
+
+        assert_eq!(2, info.len);
+        assert_eq!(
+            vec![Some("Run".to_string()), Some("Init".to_string())],
+            info.labels
+        );
+        assert_eq!(vec![Some(0), Some(10)], info.priorities);
+    }
+
+    trait TraitHandler {
+        fn handle_load(v: u32);
+        fn handle_save_all(first: u32, second: u32);
+    }
+
+    #[derive(Execute)]
+    #[default_handler(trait = TraitHandler)]
+    enum TraitDispatchedPipeline {
+        Load(u32),
+        #[handler(ref trait = TraitRefHandler)]
+        Store(u32),
+        SaveAll {
+            first: u32,
+            second: u32,
+        },
+    }
+
+    impl TraitHandler for TraitDispatchedPipeline {
+        fn handle_load(v: u32) {
+            RecordingStep(v).execute()
+        }
+
+        fn handle_save_all(first: u32, second: u32) {
+            RecordingStep(first * second).execute()
+        }
+    }
+
+    trait TraitRefHandler {
+        fn handle_store(v: &u32);
+    }
+
+    impl TraitRefHandler for TraitDispatchedPipeline {
+        fn handle_store(v: &u32) {
+            RecordingStep(*v + 100).execute()
+        }
+    }
+
+    #[test]
+    fn derived_trait_handler_works() {
+        let pipeline = vec![
+            TraitDispatchedPipeline::Load(1),
+            TraitDispatchedPipeline::Store(2),
+            TraitDispatchedPipeline::SaveAll {
+                first: 3,
+                second: 4,
+            },
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 102, 12], recorded_steps());
+    }
+
+    #[derive(Execute, Describe, Labeled, Prioritized, Constructors)]
+    enum EmptyPipeline {}
+
+    #[derive(Execute)]
+    #[execute_in_place]
+    enum EmptyInPlacePipeline {}
+
+    #[test]
+    fn derived_empty_enum_works() {
+        let pipeline: PipelineVec<EmptyPipeline> = PipelineVec::new();
+
+        assert!(pipeline.describe().is_empty());
+        assert_eq!(0, pipeline.info_with_priorities().len);
+        pipeline.execute();
+
+        let mut pipeline: PipelineVec<EmptyInPlacePipeline> = PipelineVec::new();
+        ExecuteInPlace::execute(&mut pipeline);
+    }
+
+    /// Commits by recording its value, but only prepares values below 10.
+    struct TransactionStep(u32);
+
+    impl TwoPhase for TransactionStep {
+        fn prepare(&self) -> Result<(), String> {
+            match self.0 < 10 {
+                true => Ok(()),
+                false => Err(format!("{} is too large", self.0)),
+            }
+        }
+
+        fn commit(self) {
+            RecordingStep(self.0).execute()
+        }
+    }
+
+    #[test]
+    fn pipeline_two_phase_works() {
+        let pipeline: PipelineVec<_> = (1..=3).map(TransactionStep).collect();
+
+        assert_eq!(Ok(()), pipeline.execute_two_phase());
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+
+        let pipeline: PipelineVec<_> = [4, 10, 5, 11]
+            .iter()
+            .copied()
+            .map(TransactionStep)
+            .collect();
+
+        assert_eq!(
+            Err((1, "10 is too large".to_string())),
+            pipeline.execute_two_phase()
+        );
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn execute_from_channel_works() {
+        let (tx, rx) = std::sync::mpsc::channel();
+        let producer = std::thread::spawn(move || {
+            for v in 1..=3 {
+                tx.send(RecordingStep(v)).unwrap();
+            }
+        });
+
+        execute_from_channel(rx);
+        producer.join().unwrap();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[execute_ref]
+    enum BorrowedPipeline {
+        #[handler(record)]
+        #[handler(ref record)]
+        Record(u32),
+        #[handler(record_name)]
+        Named { name: String, scale: u32 },
+    }
+
+    impl BorrowedPipeline {
+        fn record(v: &u32) {
+            RecordingStep(*v).execute()
+        }
+
+        fn record_name(name: &str, scale: &u32) {
+            RecordingStep(name.len() as u32 * scale).execute()
+        }
+    }
+
+    #[test]
+    fn derived_execute_ref_works() {
+        let step = BorrowedPipeline::Named {
+            name: "four".to_string(),
+            scale: 10,
+        };
+
+        step.execute_ref();
+        step.execute_ref();
+        BorrowedPipeline::Record(3).execute_ref();
+
+        assert_eq!(vec![40, 40, 3, 3], recorded_steps());
+        assert!(matches!(step, BorrowedPipeline::Named { scale: 10, .. }));
+    }
+
+    #[test]
+    fn derived_execute_ref_pipeline_works() {
+        let pipeline = PipelineVec::from(vec![
+            BorrowedPipeline::Record(3),
+            BorrowedPipeline::Named {
+                name: "four".to_string(),
+                scale: 10,
+            },
+        ]);
+
+        pipeline.execute_ref();
+        pipeline.execute_ref();
+
+        assert_eq!(vec![3, 3, 40, 3, 3, 40], recorded_steps());
+        assert_eq!(2, pipeline.len());
+    }
+
+    #[derive(Execute)]
+    enum MixedPassingPipeline {
+        #[handler(record_name; ref, val)]
+        Named(String, u32),
+        #[handler(ref record_name; 1, val 2)]
+        Reordered(String, u32),
+        #[handler(record_scaled; val 2, ref 1)]
+        Scaled { name: String, scale: u32 },
+    }
+
+    impl MixedPassingPipeline {
+        fn record_name(name: &str, extra: u32) {
+            RecordingStep(name.len() as u32 + extra).execute()
+        }
+
+        fn record_scaled(scale: u32, name: &str) {
+            RecordingStep(name.len() as u32 * scale).execute()
+        }
+    }
+
+    #[test]
+    fn derived_per_field_passing_works() {
+        let pipeline = vec![
+            MixedPassingPipeline::Named("one".to_string(), 10),
+            MixedPassingPipeline::Reordered("three".to_string(), 20),
+            MixedPassingPipeline::Scaled {
+                name: "two".to_string(),
+                scale: 3,
+            },
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![13, 25, 9], recorded_steps());
+    }
+
+    #[test]
+    fn pipeline_with_capacity_works() {
+        let mut pipeline = PipelineVec::with_capacity(2);
+        assert!(pipeline.is_empty());
+
+        pipeline.push(RecordingStep(1));
+        pipeline.reserve(1);
+        pipeline.push(RecordingStep(2));
+        pipeline.execute();
+
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    struct ScanConfig {
+        bonus: u32,
+    }
+
+    /// Adds its value plus the configured bonus to the running total, returning the total so far.
+    struct ScanStep(u32);
+
+    impl ExecuteScan<ScanConfig, u32> for ScanStep {
+        type Output = u32;
+
+        fn execute_scan(self, ctx: &ScanConfig, acc: &mut u32) -> u32 {
+            *acc += self.0 + ctx.bonus;
+            *acc
+        }
+    }
+
+    #[test]
+    fn pipeline_execute_scan_works() {
+        let config = ScanConfig { bonus: 10 };
+        let mut total = 0;
+        let pipeline: PipelineVec<_> = (1..=3).map(ScanStep).collect();
+
+        assert_eq!(vec![11, 23, 36], pipeline.execute_scan(&config, &mut total));
+        assert_eq!(36, total);
+    }
+
+    /// A custom collection of steps, which gets `into_pipeline()` from `impl_into_pipeline!`.
+    struct StepList<T>(Vec<T>);
+
+    impl<T> IntoIterator for StepList<T> {
+        type Item = T;
+        type IntoIter = std::vec::IntoIter<T>;
+
+        fn into_iter(self) -> Self::IntoIter {
+            self.0.into_iter()
+        }
+    }
+
+    crate::impl_into_pipeline!(StepList);
+
+    mod arena {
+        // a borrowed container of steps, which executes clones of them
+        pub struct Arena<'a, T>(pub &'a [T]);
+
+        impl<'a, T: Clone> IntoIterator for Arena<'a, T> {
+            type Item = T;
+            type IntoIter = std::iter::Cloned<std::slice::Iter<'a, T>>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.iter().cloned()
+            }
+        }
+
+        // a container that isn't generic over its steps
+        pub(super) struct Batch(pub(super) Vec<super::RecordingStep>);
+
+        impl IntoIterator for Batch {
+            type Item = super::RecordingStep;
+            type IntoIter = std::vec::IntoIter<super::RecordingStep>;
+
+            fn into_iter(self) -> Self::IntoIter {
+                self.0.into_iter()
+            }
+        }
+    }
+
+    crate::impl_into_pipeline!(['a, T] arena::Arena<'a, T>);
+    crate::impl_into_pipeline!(arena::Batch);
+
+    #[test]
+    fn impl_into_pipeline_works() {
+        StepList(vec![RecordingStep(1), RecordingStep(2)])
+            .into_pipeline()
+            .execute();
+
+        let pipeline: PipelineVecWith<_, u32> =
+            StepList(vec![ScaledPipeline::Record(3)]).into_pipeline();
+        pipeline.execute(&10);
+
+        assert_eq!(vec![1, 2, 30], recorded_steps());
+    }
+
+    #[test]
+    fn impl_into_pipeline_with_generics_works() {
+        let steps = [RecordingStep(1), RecordingStep(2)];
+        arena::Arena(&steps).into_pipeline().execute();
+        arena::Batch(vec![RecordingStep(3)])
+            .into_pipeline()
+            .execute();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+    }
+
+    #[test]
+    #[cfg(feature = "std")]
+    fn pipeline_execute_reported_works() {
+        let pipeline: PipelineVec<_> = (1..=3).map(RecordingStep).collect();
+
+        let report = pipeline.execute_reported();
+
+        assert_eq!(vec![1, 2, 3], recorded_steps());
+        assert_eq!(3, report.total_steps);
+        assert_eq!(3, report.steps_run);
+        assert_eq!(3, report.step_durations.len());
+        assert!(report.total_duration >= report.step_durations.iter().sum());
+    }
+
+    mod module_handlers {
+        use super::RecordingStep;
+        use crate::Execute;
+
+        pub fn handle_load(v: u32) {
+            RecordingStep(v).execute()
+        }
+
+        pub fn handle_save(v: u32) {
+            RecordingStep(v * 10).execute()
+        }
+    }
+
+    #[derive(Execute)]
+    #[handlers_in(module_handlers)]
+    #[handler_prefix("handle_")]
+    enum ModuleHandledPipeline {
+        #[handler(handle_load)]
+        Load(u32),
+        Save(u32),
+        #[handler(ModuleHandledPipeline::own_handler)]
+        Own(u32),
+    }
+
+    impl ModuleHandledPipeline {
+        fn own_handler(v: u32) {
+            RecordingStep(v * 100).execute()
+        }
+    }
+
+    #[test]
+    fn derived_handlers_in_works() {
+        let pipeline = vec![
+            ModuleHandledPipeline::Load(1),
+            ModuleHandledPipeline::Save(2),
+            ModuleHandledPipeline::Own(3),
+        ]
+        .into_pipeline();
 
-        let mut data = MutDataPipelineData::default();
-        pipeline.execute(&mut data);
+        pipeline.execute();
 
-        assert_eq!(12.0, data.one_value);
-        assert_eq!(1, data.two_count);
+        assert_eq!(vec![1, 20, 300], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[catch_all(Self::fallback)]
+    enum CatchAllPipeline {
+        #[handler(crate::tests::record)]
+        Known(u32),
+        // handled by the catch all handler until it gets a handler of its own
+        #[allow(dead_code)]
+        Unhandled(u32),
+    }
+
+    impl CatchAllPipeline {
+        fn fallback() {
+            RecordingStep(0).execute()
+        }
+    }
+
+    #[test]
+    fn derived_catch_all_works() {
+        let pipeline = vec![
+            CatchAllPipeline::Known(1),
+            CatchAllPipeline::Unhandled(2),
+            CatchAllPipeline::Known(3),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![1, 0, 3], recorded_steps());
+    }
+
+    #[derive(Execute, Phased)]
+    enum PhasedPipeline {
+        #[handler(crate::tests::record)]
+        #[phase("setup")]
+        Allocate(u32),
+        #[handler(crate::tests::record)]
+        #[phase("run")]
+        Run(u32),
+        #[handler(crate::tests::record)]
+        #[phase("teardown")]
+        Free(u32),
+        #[handler(crate::tests::record)]
+        Log(u32),
+    }
+
+    #[test]
+    fn pipeline_execute_phase_works() {
+        let pipeline = vec![
+            PhasedPipeline::Allocate(1),
+            PhasedPipeline::Log(2),
+            PhasedPipeline::Run(3),
+            PhasedPipeline::Allocate(4),
+            PhasedPipeline::Free(5),
+        ]
+        .into_pipeline();
+
+        assert_eq!(vec!["setup", "run", "teardown"], pipeline.phases());
+
+        pipeline.execute_phase("setup");
+
+        assert_eq!(vec![1, 4], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    enum InlineBlockPipeline {
+        #[handler({ RecordingStep(__1 + __2).execute() })]
+        Sum(u32, u32),
+        #[handler({ RecordingStep(width * height).execute() })]
+        Resize { width: u32, height: u32 },
+        #[handler(crate::tests::record)]
+        #[handler({ RecordingStep(0).execute() })]
+        Twice(u32),
+    }
+
+    #[test]
+    fn derived_inline_block_handler_works() {
+        let pipeline = vec![
+            InlineBlockPipeline::Sum(1, 2),
+            InlineBlockPipeline::Resize {
+                width: 4,
+                height: 5,
+            },
+            InlineBlockPipeline::Twice(6),
+        ]
+        .into_pipeline();
+
+        pipeline.execute();
+
+        assert_eq!(vec![3, 20, 6, 0], recorded_steps());
+    }
+
+    #[derive(Execute, HasDeps)]
+    enum DependentPipeline {
+        #[handler(crate::tests::record)]
+        Init(u32),
+        #[handler(crate::tests::record)]
+        #[after("Init")]
+        Load(u32),
+        #[handler(crate::tests::record)]
+        #[after("Init", "Load")]
+        Run(u32),
+        #[handler(crate::tests::record)]
+        Log(u32),
+    }
+
+    #[derive(Execute, HasDeps)]
+    enum CyclicPipeline {
+        #[handler(RecordingStep::execute)]
+        #[after("Second")]
+        First(RecordingStep),
+        #[handler(RecordingStep::execute)]
+        #[after("First")]
+        Second(RecordingStep),
+        #[handler(RecordingStep::execute)]
+        Free(RecordingStep),
+    }
+
+    #[test]
+    fn pipeline_execute_topo_works() {
+        let pipeline = vec![
+            DependentPipeline::Run(1),
+            DependentPipeline::Log(2),
+            DependentPipeline::Load(3),
+            DependentPipeline::Init(4),
+            DependentPipeline::Load(5),
+        ]
+        .into_pipeline();
+
+        assert_eq!(Ok(()), pipeline.execute_topo());
+        assert_eq!(vec![2, 4, 3, 5, 1], recorded_steps());
+
+        let cyclic = vec![
+            CyclicPipeline::Free(RecordingStep(6)),
+            CyclicPipeline::First(RecordingStep(7)),
+            CyclicPipeline::Second(RecordingStep(8)),
+        ]
+        .into_pipeline();
+
+        assert_eq!(Err(CycleError { steps: vec![1, 2] }), cyclic.execute_topo());
+        assert_eq!(vec![2, 4, 3, 5, 1], recorded_steps());
+    }
+
+    #[derive(Execute)]
+    #[emit_dispatch_fn]
+    enum DispatchFnPipeline {
+        #[handler(crate::tests::record)]
+        One(u32),
+        #[handler(crate::tests::record)]
+        Two(u32),
+    }
+
+    #[test]
+    fn derived_dispatch_fn_works() {
+        DispatchFnPipeline::One(1).dispatch();
+        DispatchFnPipeline::Two(2).execute();
+
+        assert_eq!(vec![1, 2], recorded_steps());
+    }
+
+    #[cfg(feature = "tracing")]
+    mod instrumented {
+        use super::{recorded_steps, RecordingStep};
+        use crate::IntoPipelineVec;
+        use std::fmt::Debug;
+        use std::sync::atomic::{AtomicU64, Ordering};
+        use std::sync::{Arc, Mutex};
+        use tracing::field::{Field, Visit};
+        use tracing::span::{Attributes, Id, Record};
+        use tracing::{Event, Metadata, Subscriber};
+
+        /// Formats the fields of a span or event as `name=value` pairs.
+        struct FieldFormatter(Vec<String>);
+
+        impl Visit for FieldFormatter {
+            fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+                self.0.push(format!("{}={:?}", field.name(), value));
+            }
+        }
+
+        /// A subscriber that records each new span and event as a line of text.
+        #[derive(Default)]
+        struct CapturingSubscriber {
+            next_id: AtomicU64,
+            lines: Arc<Mutex<Vec<String>>>,
+        }
+
+        impl Subscriber for CapturingSubscriber {
+            fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+                true
+            }
+
+            fn new_span(&self, span: &Attributes<'_>) -> Id {
+                let mut fields = FieldFormatter(vec![]);
+                span.record(&mut fields);
+                self.lines.lock().unwrap().push(format!(
+                    "span {} {}",
+                    span.metadata().name(),
+                    fields.0.join(" ")
+                ));
+
+                Id::from_u64(self.next_id.fetch_add(1, Ordering::SeqCst) + 1)
+            }
+
+            fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+            fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+            fn event(&self, event: &Event<'_>) {
+                let mut fields = FieldFormatter(vec![]);
+                event.record(&mut fields);
+                self.lines
+                    .lock()
+                    .unwrap()
+                    .push(format!("event {}", fields.0.join(" ")));
+            }
+
+            fn enter(&self, _span: &Id) {}
+
+            fn exit(&self, _span: &Id) {}
+        }
+
+        #[test]
+        fn pipeline_execute_instrumented_emits_spans() {
+            let subscriber = CapturingSubscriber::default();
+            let lines = subscriber.lines.clone();
+
+            tracing::subscriber::with_default(subscriber, || {
+                vec![RecordingStep(1), RecordingStep(2)]
+                    .into_pipeline()
+                    .execute_instrumented("setup");
+            });
+
+            assert_eq!(
+                vec![
+                    "span pipeline name=\"setup\"",
+                    "span step index=0",
+                    "event message=executing step",
+                    "event message=executed step",
+                    "span step index=1",
+                    "event message=executing step",
+                    "event message=executed step",
+                ],
+                *lines.lock().unwrap()
+            );
+            assert_eq!(vec![1, 2], recorded_steps());
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    mod parallel {
+        use crate::{Execute, IntoPipelineVec};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+        use std::sync::Mutex;
+
+        static PARALLEL_TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+        struct ParallelStep(usize);
+
+        impl Execute for ParallelStep {
+            fn execute(self) {
+                PARALLEL_TOTAL.fetch_add(self.0, Ordering::SeqCst);
+            }
+        }
+
+        #[test]
+        fn pipeline_execute_parallel_works() {
+            let pipeline = (1..=1000)
+                .map(ParallelStep)
+                .collect::<Vec<_>>()
+                .into_pipeline();
+
+            pipeline.execute_parallel();
+
+            assert_eq!(500500, PARALLEL_TOTAL.load(Ordering::SeqCst));
+        }
+
+        static CHUNKED_ORDER: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+        struct ChunkedStep(usize);
+
+        impl Execute for ChunkedStep {
+            fn execute(self) {
+                CHUNKED_ORDER.lock().unwrap().push(self.0);
+            }
+        }
+
+        #[test]
+        fn pipeline_execute_chunked_works() {
+            let pipeline = (0..8).map(ChunkedStep).collect::<Vec<_>>().into_pipeline();
+
+            pipeline.execute_chunked(3);
+
+            // steps within a batch may run in any order, but batches run one after the other
+            let order = CHUNKED_ORDER.lock().unwrap().clone();
+            let batches: Vec<Vec<usize>> = order
+                .chunks(3)
+                .map(|batch| {
+                    let mut batch = batch.to_vec();
+                    batch.sort_unstable();
+                    batch
+                })
+                .collect();
+
+            assert_eq!(vec![vec![0, 1, 2], vec![3, 4, 5], vec![6, 7]], batches);
+        }
+
+        #[test]
+        #[should_panic(expected = "non-zero chunk size")]
+        fn pipeline_execute_chunked_panics_on_zero_size() {
+            let pipeline = vec![ChunkedStep(0)].into_pipeline();
+
+            pipeline.execute_chunked(0);
+        }
+    }
+
+    #[cfg(feature = "async")]
+    mod asynchronous {
+        use super::{recorded_steps, RecordingStep};
+        use crate::{Execute, ExecuteAsync, ExecuteFuture, IntoPipelineVec, PipelineVec};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        impl ExecuteAsync for RecordingStep {
+            fn execute_async(self) -> ExecuteFuture {
+                Box::pin(async move {
+                    // give the runtime a chance to run something else in between steps
+                    tokio::task::yield_now().await;
+                    self.execute()
+                })
+            }
+        }
+
+        #[tokio::test]
+        async fn pipeline_execute_async_works() {
+            let pipeline =
+                vec![RecordingStep(1), RecordingStep(2), RecordingStep(3)].into_pipeline();
+
+            pipeline.execute_async().await;
+
+            assert_eq!(vec![1, 2, 3], recorded_steps());
+        }
+
+        static CONCURRENT_TOTAL: AtomicUsize = AtomicUsize::new(0);
+
+        struct CountingStep(usize);
+
+        impl ExecuteAsync for CountingStep {
+            fn execute_async(self) -> ExecuteFuture {
+                Box::pin(async move {
+                    tokio::task::yield_now().await;
+                    CONCURRENT_TOTAL.fetch_add(self.0, Ordering::SeqCst);
+                })
+            }
+        }
+
+        #[tokio::test]
+        async fn pipeline_execute_concurrent_works() {
+            let pipeline: PipelineVec<_> = (1..=1000).map(CountingStep).collect();
+
+            pipeline.execute_concurrent().await;
+
+            assert_eq!(500500, CONCURRENT_TOTAL.load(Ordering::SeqCst));
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    mod serialization {
+        use super::{recorded_steps, RecordingStep};
+        use crate::{
+            Execute, ExecuteWithMut, IntoPipelineVec, IntoPipelineVecWithMut, PipelineVec,
+            PipelineVecWith,
+        };
+        use enum_pipeline_derive::Execute;
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Execute, Serialize, Deserialize)]
+        enum SerializedPipeline {
+            #[handler(crate::tests::record)]
+            Record(u32),
+            #[handler(record_twice)]
+            RecordTwice { value: u32 },
+        }
+
+        impl SerializedPipeline {
+            fn record_twice(value: u32) {
+                RecordingStep(value).execute();
+                RecordingStep(value).execute();
+            }
+        }
+
+        #[test]
+        fn pipeline_serde_round_trip_works() {
+            let pipeline = vec![
+                SerializedPipeline::Record(1),
+                SerializedPipeline::RecordTwice { value: 2 },
+            ]
+            .into_pipeline();
+
+            let json = serde_json::to_string(&pipeline).unwrap();
+            assert_eq!(r#"[{"Record":1},{"RecordTwice":{"value":2}}]"#, json);
+
+            let pipeline: PipelineVec<SerializedPipeline> = serde_json::from_str(&json).unwrap();
+            pipeline.execute();
+
+            assert_eq!(vec![1, 2, 2], recorded_steps());
+        }
+
+        // the argument type isn't (de)serializable, which the skipped `PhantomData` doesn't require
+        #[derive(Default)]
+        struct Output(Vec<u32>);
+
+        #[derive(Execute, Serialize, Deserialize)]
+        #[execute_with_mut(Output)]
+        enum SerializedWithPipeline {
+            #[handler(push)]
+            Push(u32),
+        }
+
+        impl SerializedWithPipeline {
+            fn push(v: u32, arg: &mut Output) {
+                arg.0.push(v)
+            }
+        }
+
+        #[test]
+        fn pipeline_with_serde_round_trip_works() {
+            let pipeline: PipelineVecWith<_, Output> = vec![
+                SerializedWithPipeline::Push(1),
+                SerializedWithPipeline::Push(2),
+            ]
+            .into_pipeline();
+
+            let json = serde_json::to_string(&pipeline).unwrap();
+            assert_eq!(r#"[{"Push":1},{"Push":2}]"#, json);
+
+            let pipeline: PipelineVecWith<SerializedWithPipeline, Output> =
+                serde_json::from_str(&json).unwrap();
+            let mut output = Output::default();
+            pipeline.execute(&mut output);
+
+            assert_eq!(vec![1, 2], output.0);
+        }
+    }
+
+    #[cfg(feature = "smallvec")]
+    mod small {
+        use super::{recorded_steps, RecordingStep};
+        use crate::{Execute, PipelineSeq, PipelineSmallVec};
+        use smallvec::{smallvec, SmallVec};
+
+        #[test]
+        fn small_vec_pipeline_works() {
+            let steps: SmallVec<[RecordingStep; 4]> = smallvec![RecordingStep(1), RecordingStep(2)];
+            let pipeline: PipelineSmallVec<[RecordingStep; 4]> = PipelineSeq::from_steps(steps);
+
+            assert_eq!(
+                "enum_pipeline::tests::RecordingStep",
+                super::step_type_name(&pipeline)
+            );
+
+            pipeline.execute();
+
+            assert_eq!(vec![1, 2], recorded_steps());
+        }
+
+        #[test]
+        fn small_vec_pipeline_methods_work() {
+            let mut pipeline: PipelineSmallVec<[RecordingStep; 4]> =
+                PipelineSeq::from_steps(SmallVec::new());
+            pipeline.push(RecordingStep(1));
+            pipeline.push(RecordingStep(2));
+            pipeline.push(RecordingStep(3));
+
+            assert_eq!(3, pipeline.len());
+            assert_eq!(Some(&RecordingStep(2)), pipeline.get(1));
+            assert_eq!(
+                "PipelineSeq { steps: [RecordingStep(1), RecordingStep(2), RecordingStep(3)] }",
+                format!("{:?}", pipeline)
+            );
+
+            pipeline.execute_rev();
+
+            assert_eq!(vec![3, 2, 1], recorded_steps());
+        }
     }
 }
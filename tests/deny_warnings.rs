@@ -0,0 +1,45 @@
+//! Checks that the derived impls don't trip any lints in a crate that denies warnings.
+#![deny(warnings, clippy::all, clippy::pedantic)]
+
+use enum_pipeline::{Execute, IntoPipelineVec};
+use enum_pipeline_derive::Execute;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static TOTAL: AtomicU32 = AtomicU32::new(0);
+
+/// A pipeline whose docs are repeated on the derived impl.
+#[derive(Execute, Clone)]
+#[allow(clippy::enum_variant_names)]
+enum LintedPipeline {
+    #[handler(add)]
+    #[handler(add)]
+    AddTwice(u32),
+    #[handler(ref add_ref)]
+    AddRef { amount: u32 },
+    #[skip]
+    AddNothing,
+}
+
+impl LintedPipeline {
+    fn add(amount: u32) {
+        TOTAL.fetch_add(amount, Ordering::SeqCst);
+    }
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn add_ref(amount: &u32) {
+        Self::add(*amount);
+    }
+}
+
+#[test]
+fn derive_under_deny_warnings_works() {
+    vec![
+        LintedPipeline::AddTwice(1),
+        LintedPipeline::AddRef { amount: 3 },
+        LintedPipeline::AddNothing,
+    ]
+    .into_pipeline()
+    .execute();
+
+    assert_eq!(5, TOTAL.load(Ordering::SeqCst));
+}
@@ -0,0 +1,38 @@
+//! Checks that the prelude alone is enough to derive, build and execute a pipeline.
+use enum_pipeline::prelude::*;
+use std::sync::atomic::{AtomicU32, Ordering};
+
+static TOTAL: AtomicU32 = AtomicU32::new(0);
+
+#[derive(Execute, Labeled)]
+enum PreludePipeline {
+    #[handler(add)]
+    #[label("Add an amount")]
+    Add(u32),
+    #[handler(ref add_twice)]
+    AddTwice(u32),
+}
+
+impl PreludePipeline {
+    fn add(amount: u32) {
+        TOTAL.fetch_add(amount, Ordering::SeqCst);
+    }
+
+    fn add_twice(amount: &u32) {
+        TOTAL.fetch_add(amount * 2, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn prelude_pipeline_works() {
+    let pipeline = vec![PreludePipeline::Add(1), PreludePipeline::AddTwice(2)].into_pipeline();
+
+    assert_eq!(
+        vec!["Add an amount", "AddTwice"],
+        pipeline.iter().map(Labeled::label).collect::<Vec<_>>()
+    );
+
+    pipeline.execute();
+
+    assert_eq!(5, TOTAL.load(Ordering::SeqCst));
+}
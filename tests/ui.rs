@@ -0,0 +1,7 @@
+// compile-fail cases for the derive macro, pinning the diagnostics it produces.
+// regenerate the .stderr snapshots with `TRYBUILD=overwrite cargo test --test ui`.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
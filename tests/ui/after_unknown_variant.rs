@@ -0,0 +1,10 @@
+use enum_pipeline_derive::HasDeps;
+
+#[derive(HasDeps)]
+enum Pipeline {
+    Init,
+    #[after("Setup")]
+    Run,
+}
+
+fn main() {}
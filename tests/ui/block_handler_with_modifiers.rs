@@ -0,0 +1,9 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(ref { println!("{}", __1) })]
+    One(u32),
+}
+
+fn main() {}
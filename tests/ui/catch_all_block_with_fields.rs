@@ -0,0 +1,9 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[catch_all({ println!("{}", __1) })]
+enum Pipeline {
+    One(u32),
+}
+
+fn main() {}
@@ -0,0 +1,11 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[catch_all(trait = Fallback)]
+enum Pipeline {
+    One(u32),
+}
+
+trait Fallback {}
+
+fn main() {}
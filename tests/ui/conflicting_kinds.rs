@@ -0,0 +1,15 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[execute_with(u32)]
+#[try_execute(String)]
+enum Pipeline {
+    #[handler(handle_one)]
+    One,
+}
+
+impl Pipeline {
+    fn handle_one(_arg: &u32) {}
+}
+
+fn main() {}
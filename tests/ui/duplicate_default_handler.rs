@@ -0,0 +1,14 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[default_handler(handle_any)]
+#[default_handler(handle_any)]
+enum Pipeline {
+    One,
+}
+
+impl Pipeline {
+    fn handle_any() {}
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(handle_one; ref)]
+    One(String, u32),
+}
+
+impl Pipeline {
+    fn handle_one(_name: &String) {}
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(handle_one; ref 2, val)]
+    One(u32, String),
+}
+
+impl Pipeline {
+    fn handle_one(_name: &String, _count: u32) {}
+}
+
+fn main() {}
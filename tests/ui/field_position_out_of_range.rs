@@ -0,0 +1,13 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(handle_one; 2, 3)]
+    One(f32, f32),
+}
+
+impl Pipeline {
+    fn handle_one(_y: f32, _z: f32) {}
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use enum_pipeline::{Execute, IntoPipelineVec};
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(handle_one)]
+    One(f32, f32),
+}
+
+impl Pipeline {
+    fn handle_one(_v: f32) {}
+}
+
+fn main() {
+    vec![Pipeline::One(1.0, 2.0)].into_pipeline().execute();
+}
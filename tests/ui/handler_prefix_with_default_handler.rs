@@ -0,0 +1,14 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[handler_prefix("handle_")]
+#[default_handler(handle_any)]
+enum Pipeline {
+    One,
+}
+
+impl Pipeline {
+    fn handle_any() {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(1 + 2)]
+    One,
+}
+
+fn main() {}
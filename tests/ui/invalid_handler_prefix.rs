@@ -0,0 +1,9 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[handler_prefix("handle-")]
+enum Pipeline {
+    One,
+}
+
+fn main() {}
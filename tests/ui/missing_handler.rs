@@ -0,0 +1,14 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(handle_one)]
+    One,
+    Two(f32),
+}
+
+impl Pipeline {
+    fn handle_one() {}
+}
+
+fn main() {}
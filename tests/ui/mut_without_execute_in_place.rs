@@ -0,0 +1,13 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(mut handle_one)]
+    One(u32),
+}
+
+impl Pipeline {
+    fn handle_one(_v: &mut u32) {}
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+struct Pipeline {
+    value: f32,
+}
+
+fn main() {}
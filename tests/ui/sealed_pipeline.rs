@@ -0,0 +1,9 @@
+use enum_pipeline::Pipeline;
+
+struct MyPipeline;
+
+impl Pipeline for MyPipeline {
+    type Step = ();
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[execute_collect(u32)]
+enum Pipeline {
+    #[handler(handle_one)]
+    One,
+    #[skip]
+    Two,
+}
+
+impl Pipeline {
+    fn handle_one() -> u32 {
+        1
+    }
+}
+
+fn main() {}
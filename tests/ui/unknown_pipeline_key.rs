@@ -0,0 +1,14 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+#[pipeline(name = "Render")]
+enum Pipeline {
+    #[handler(handle_one)]
+    One,
+}
+
+impl Pipeline {
+    fn handle_one() {}
+}
+
+fn main() {}
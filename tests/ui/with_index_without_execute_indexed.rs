@@ -0,0 +1,13 @@
+use enum_pipeline_derive::Execute;
+
+#[derive(Execute)]
+enum Pipeline {
+    #[handler(with_index handle_one)]
+    One,
+}
+
+impl Pipeline {
+    fn handle_one(_index: usize) {}
+}
+
+fn main() {}